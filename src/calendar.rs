@@ -0,0 +1,336 @@
+//! Calendar heatmap of logging activity.
+//!
+//! This module prints a month grid or a GitHub-contribution style year view
+//! in the terminal, shading each day by how much was logged.
+
+use crate::{
+    config::Config,
+    date::{DateRange, parse_weekday},
+    daylog::{DayLog, Entry},
+    theme::Theme,
+};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use clap::ValueEnum;
+use std::collections::HashMap;
+use termcolor::{Color, ColorSpec, WriteColor};
+
+/// What the heatmap shading is based on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum HeatMetric {
+    /// Number of entries per day
+    #[default]
+    Entries,
+    /// Number of words written per day
+    Words,
+}
+
+/// Colors for activity levels 1 to 4, from least to most active.
+const LEVEL_COLORS: [u8; 4] = [22, 28, 34, 40];
+
+/// Glyphs for activity levels 0 to 4, used by the year view, and by the
+/// month view when color is off.
+const LEVEL_GLYPHS: [&str; 5] = ["·", "░", "▒", "▓", "█"];
+
+/// Activity for each day of a calendar period.
+struct Activity {
+    /// Metric value per day; days without logs are absent
+    values: HashMap<NaiveDate, usize>,
+    /// Largest value in the period, used to scale shading
+    max: usize,
+    /// Configured summary days; other days are dimmed
+    allowed_weekdays: Vec<Weekday>,
+    /// Today's date, which is highlighted
+    today: NaiveDate,
+}
+
+impl Activity {
+    /// Returns the shading level (0 to 4) for a day.
+    fn level(&self, date: NaiveDate) -> usize {
+        let value = self.values.get(&date).copied().unwrap_or(0);
+        if value == 0 || self.max == 0 {
+            0
+        } else {
+            (value * 4).div_ceil(self.max).clamp(1, 4)
+        }
+    }
+
+    /// Returns true if a day is outside the counted days: in the future or
+    /// not one of the configured summary days.
+    fn is_dimmed(&self, date: NaiveDate) -> bool {
+        date > self.today || !self.allowed_weekdays.contains(&date.weekday())
+    }
+
+    /// Returns the glyph for a day when color is off.
+    ///
+    /// Days that would be dimmed and have no activity are left blank, so
+    /// they still stand apart from counted days with nothing logged.
+    fn plain_glyph(&self, date: NaiveDate) -> &'static str {
+        let level = self.level(date);
+        if level == 0 && self.is_dimmed(date) {
+            " "
+        } else {
+            LEVEL_GLYPHS[level]
+        }
+    }
+
+    /// Returns the color specification for a day's cell.
+    fn cell_spec(&self, date: NaiveDate, background: bool) -> ColorSpec {
+        let mut spec = ColorSpec::new();
+        let level = self.level(date);
+
+        if level > 0 {
+            let color = Color::Ansi256(LEVEL_COLORS[level - 1]);
+            if background {
+                spec.set_bg(Some(color)).set_fg(Some(Color::White));
+            } else {
+                spec.set_fg(Some(color));
+            }
+        } else if self.is_dimmed(date) {
+            spec.set_dimmed(true);
+        }
+
+        if date == self.today {
+            spec.set_bold(true).set_underline(true);
+        }
+        spec
+    }
+}
+
+/// Displays a calendar heatmap of logging activity.
+///
+/// Shows a month grid for ranges of up to a month, and a GitHub-style year
+/// view (one column per week) for longer ranges. Each day is shaded by its
+/// number of entries or words relative to the busiest day in the period.
+/// Days not in `summary_days` and days in the future are dimmed, and today
+/// is underlined. When `out` does not support color, the month view marks
+/// each day with the same glyphs as the year view, today is marked with `*`,
+/// and dimmed days without activity are left blank.
+///
+/// # Arguments
+///
+/// * `out` - Where to write the calendar
+/// * `log_dir` - The directory containing log files
+/// * `range` - The month or year to display
+/// * `config` - Application configuration containing summary day filters
+/// * `metric` - Whether to shade by entry count or word count
+/// * `today` - The date to highlight as today
+/// * `theme` - The styles to use for headings
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - Log files cannot be read
+/// - Terminal output fails
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::calendar::{show_calendar, HeatMetric};
+/// use dailylog::config::load_config;
+/// use dailylog::date::{today, DateRange};
+/// use dailylog::theme::Theme;
+/// use termcolor::{ColorChoice, StandardStream};
+///
+/// let config = load_config()?;
+/// let range = DateRange::month(None, today())?;
+/// let mut out = StandardStream::stdout(ColorChoice::Auto);
+/// show_calendar(
+///     &mut out,
+///     &config.log_dir,
+///     &range,
+///     &config,
+///     HeatMetric::Entries,
+///     today(),
+///     &Theme::default(),
+/// )?;
+/// ```
+pub fn show_calendar(
+    out: &mut impl WriteColor,
+    log_dir: &str,
+    range: &DateRange,
+    config: &Config,
+    metric: HeatMetric,
+    today: NaiveDate,
+    theme: &Theme,
+) -> anyhow::Result<()> {
+    let mut values = HashMap::new();
+    let mut total_entries = 0;
+    let mut days_with_entries = 0;
+
+    for date in range.days_until(today) {
+        let day = DayLog::load(log_dir, date)?;
+        if day.is_empty() {
+            continue;
+        }
+        total_entries += day.entries.len();
+        days_with_entries += 1;
+        let value = match metric {
+            HeatMetric::Entries => day.entries.len(),
+            HeatMetric::Words => day.entries.iter().map(Entry::word_count).sum(),
+        };
+        values.insert(date, value);
+    }
+
+    let activity = Activity {
+        max: values.values().copied().max().unwrap_or(0),
+        values,
+        allowed_weekdays: config
+            .summary_days
+            .iter()
+            .filter_map(|day| parse_weekday(day))
+            .collect(),
+        today,
+    };
+
+    out.set_color(&theme.header)?;
+    writeln!(out, "=== {} ===", range.label)?;
+    out.reset()?;
+
+    if range.end - range.start <= Duration::days(31) {
+        print_month_grid(out, range, &activity)?;
+    } else {
+        print_year_grid(out, range, &activity)?;
+    }
+
+    // Legend and totals
+    write!(out, "\nLess ")?;
+    for level in 0..LEVEL_GLYPHS.len() {
+        let mut spec = ColorSpec::new();
+        if level > 0 {
+            spec.set_fg(Some(Color::Ansi256(LEVEL_COLORS[level - 1])));
+        }
+        out.set_color(&spec)?;
+        write!(out, "{} ", LEVEL_GLYPHS[level])?;
+    }
+    out.reset()?;
+    writeln!(
+        out,
+        "More   ({} per day, busiest: {})",
+        match metric {
+            HeatMetric::Entries => "entries",
+            HeatMetric::Words => "words",
+        },
+        activity.max
+    )?;
+    writeln!(
+        out,
+        "{} entries on {} days",
+        total_entries, days_with_entries
+    )?;
+
+    Ok(())
+}
+
+/// Prints a single-month grid with one row per week, Monday first.
+///
+/// Without color, each day number is followed by its activity glyph, and
+/// today's number is preceded by `*`.
+fn print_month_grid(
+    out: &mut impl WriteColor,
+    range: &DateRange,
+    activity: &Activity,
+) -> anyhow::Result<()> {
+    let glyphs = !out.supports_color();
+    let (weekdays, empty_cell) = if glyphs {
+        (" Mo  Tu  We  Th  Fr  Sa  Su", "    ")
+    } else {
+        (" Mo Tu We Th Fr Sa Su", "   ")
+    };
+
+    out.set_color(ColorSpec::new().set_bold(true))?;
+    writeln!(out, "{}", weekdays)?;
+    out.reset()?;
+
+    let offset = range.start.weekday().num_days_from_monday() as usize;
+    write!(out, "{}", empty_cell.repeat(offset))?;
+
+    for date in range
+        .start
+        .iter_days()
+        .take_while(|date| *date <= range.end)
+    {
+        if glyphs {
+            let number = if date == activity.today {
+                format!("*{}", date.day())
+            } else {
+                date.day().to_string()
+            };
+            write!(out, "{:>3}{}", number, activity.plain_glyph(date))?;
+        } else {
+            write!(out, " ")?;
+            out.set_color(&activity.cell_spec(date, true))?;
+            write!(out, "{:>2}", date.day())?;
+            out.reset()?;
+        }
+        if date.weekday() == Weekday::Sun {
+            writeln!(out)?;
+        }
+    }
+    if range.end.weekday() != Weekday::Sun {
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// Prints a GitHub-style grid with one row per weekday and one column per week.
+///
+/// Without color, today's glyph is followed by `*` instead of a space.
+fn print_year_grid(
+    out: &mut impl WriteColor,
+    range: &DateRange,
+    activity: &Activity,
+) -> anyhow::Result<()> {
+    let first_monday =
+        range.start - Duration::days(range.start.weekday().num_days_from_monday() as i64);
+    let weeks = ((range.end - first_monday).num_days() / 7 + 1) as usize;
+
+    // Month labels above the first week containing each month's 1st day
+    let mut labels = vec![' '; weeks * 2];
+    let mut next_free = 0;
+    for week in 0..weeks {
+        let monday = first_monday + Duration::weeks(week as i64);
+        let first_of_month = (0..7)
+            .map(|offset| monday + Duration::days(offset))
+            .find(|date| date.day() == 1 && *date >= range.start && *date <= range.end);
+        if let Some(date) = first_of_month {
+            let position = week * 2;
+            if position >= next_free {
+                for (i, ch) in date.format("%b").to_string().chars().enumerate() {
+                    if let Some(slot) = labels.get_mut(position + i) {
+                        *slot = ch;
+                    }
+                }
+                next_free = position + 4;
+            }
+        }
+    }
+    writeln!(
+        out,
+        "    {}",
+        labels.into_iter().collect::<String>().trim_end()
+    )?;
+
+    for (row, name) in ["Mon", "", "Wed", "", "Fri", "", "Sun"].iter().enumerate() {
+        write!(out, "{:<4}", name)?;
+        for week in 0..weeks {
+            let date = first_monday + Duration::weeks(week as i64) + Duration::days(row as i64);
+            if date < range.start || date > range.end {
+                write!(out, "  ")?;
+                continue;
+            }
+            if out.supports_color() {
+                out.set_color(&activity.cell_spec(date, false))?;
+                write!(out, "{}", LEVEL_GLYPHS[activity.level(date)])?;
+                out.reset()?;
+                write!(out, " ")?;
+            } else {
+                let marker = if date == activity.today { "*" } else { " " };
+                write!(out, "{}{}", activity.plain_glyph(date), marker)?;
+            }
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
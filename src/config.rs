@@ -3,9 +3,10 @@
 //! This module handles loading and parsing the TOML configuration file,
 //! providing sensible defaults for all settings.
 
+use crate::theme::ThemeConfig;
 use dirs::home_dir;
 use serde::Deserialize;
-use std::fs;
+use std::{fs, io};
 
 /// Application configuration loaded from `~/.dailylog.toml`.
 ///
@@ -29,6 +30,32 @@ use std::fs;
 ///
 /// # Days to include in summary statistics
 /// summary_days = ["monday", "tuesday", "wednesday", "thursday", "friday"]
+///
+/// # Time a new day starts; entries before it count toward the previous day
+/// day_starts_at = "04:00"
+///
+/// # Timezone for dates and entry times (default: the system's)
+/// timezone = "Europe/Berlin"
+///
+/// # Record the UTC offset in entry headers, e.g. "## 14:30 +02:00 - Title"
+/// record_utc_offset = true
+///
+/// # Pipe long output through $PAGER (default: true)
+/// pager = true
+///
+/// # Template from ~/.dailylog/templates/ used for new entries
+/// default_template = "daily"
+///
+/// # Editor command, used when $VISUAL and $EDITOR are not set (default: "vim")
+/// editor = "code --wait"
+///
+/// # Prefix of the help lines below an entry in the editor (default: "#")
+/// comment_char = ";"
+///
+/// # Colors for each kind of output, starting from a built-in theme
+/// [theme]
+/// name = "light"
+/// entry_title = { fg = "blue", bold = true }
 /// ```
 #[derive(Deserialize, Default)]
 pub struct Config {
@@ -49,6 +76,31 @@ pub struct Config {
     /// Days of the week to include in summary statistics (default: Monday-Friday)
     #[serde(default = "default_summary_days")]
     pub summary_days: Vec<String>,
+
+    /// Time of day, as `HH:MM`, at which a new day starts (default: midnight)
+    pub day_starts_at: Option<String>,
+
+    /// IANA timezone name used for dates and entry times (default: the system's)
+    pub timezone: Option<String>,
+
+    /// Whether new entry headers record the UTC offset (default: false)
+    pub record_utc_offset: Option<bool>,
+
+    /// Whether to page output taller than the terminal (default: true)
+    pub pager: Option<bool>,
+
+    /// Name of the template used for new entries written in the editor (default: none)
+    pub default_template: Option<String>,
+
+    /// Editor command used when `$VISUAL` and `$EDITOR` are not set (default: `vim`)
+    pub editor: Option<String>,
+
+    /// Prefix of the help lines below an entry in the editor (default: `#`)
+    pub comment_char: Option<String>,
+
+    /// Color theme for terminal output (default: the "default" theme)
+    #[serde(default)]
+    pub theme: ThemeConfig,
 }
 
 /// Returns the default log directory path.
@@ -81,16 +133,14 @@ fn default_summary_days() -> Vec<String> {
 
 /// Loads configuration from `~/.dailylog.toml`.
 ///
-/// If the configuration file doesn't exist or cannot be parsed,
-/// returns a default configuration with sensible defaults.
-/// This ensures the application works out-of-the-box without
-/// requiring any configuration.
+/// If the configuration file doesn't exist, returns a default configuration
+/// with sensible defaults. This ensures the application works out-of-the-box
+/// without requiring any configuration. If it exists but cannot be read or
+/// parsed, a warning is printed and the defaults are used as well.
 ///
 /// # Errors
 ///
 /// Returns an error only if the home directory cannot be determined.
-/// All other errors (missing file, parse errors) are handled gracefully
-/// by falling back to defaults.
 ///
 /// # Example
 ///
@@ -104,6 +154,19 @@ pub fn load_config() -> anyhow::Result<Config> {
     let config_path = home_dir()
         .ok_or_else(|| anyhow::anyhow!("Cannot determine home directory"))?
         .join(".dailylog.toml");
-    let config_str = fs::read_to_string(&config_path).unwrap_or_default();
-    Ok(toml::from_str(&config_str).unwrap_or_default())
+    let config_str = match fs::read_to_string(&config_path) {
+        Ok(config_str) => config_str,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            eprintln!("Warning: Failed to read {:?}: {}. Using the default settings.", config_path, e);
+            String::new()
+        }
+    };
+    Ok(toml::from_str(&config_str).unwrap_or_else(|e| {
+        eprintln!(
+            "Warning: Invalid configuration in {:?}: {}Using the default settings.",
+            config_path, e
+        );
+        Config::default()
+    }))
 }
\ No newline at end of file
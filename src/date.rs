@@ -0,0 +1,652 @@
+//! Date targeting and parsing.
+//!
+//! This module turns user-supplied date expressions into concrete dates,
+//! so any command can operate on a day other than today. Supported forms are
+//! absolute dates (`YYYY-MM-DD`), relative offsets (`-3d`, `-2w`), the words
+//! `today` and `yesterday`, and weekday names (`friday`, `last friday`).
+//!
+//! It also owns the clock: what "now" and "today" are, given the configured
+//! timezone and the time of day at which a new day starts.
+
+use crate::config::Config;
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, Months, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use std::sync::OnceLock;
+
+/// How the current date and time are worked out, from the config file.
+#[derive(Debug, Clone, Copy, Default)]
+struct Clock {
+    /// When a new day starts (`day_starts_at`, default: midnight)
+    day_starts_at: NaiveTime,
+    /// The timezone to log in (`timezone`, default: the system's)
+    timezone: Option<Tz>,
+    /// Whether entry headers record the UTC offset (`record_utc_offset`)
+    record_utc_offset: bool,
+}
+
+/// The clock set up by `configure_clock`.
+static CLOCK: OnceLock<Clock> = OnceLock::new();
+
+/// Returns the configured clock, or the system clock if none was set up.
+fn clock() -> Clock {
+    CLOCK.get().copied().unwrap_or_default()
+}
+
+/// Sets up the current date and time from the config file.
+///
+/// - `day_starts_at`: until this time of day, the previous day is still
+///   "today". An entry logged at 1am with `day_starts_at = "04:00"` goes into
+///   the previous day's log, and yesterday, summary windows and streaks are
+///   all counted the same way.
+/// - `timezone`: an IANA timezone name such as `Europe/Berlin`, used instead
+///   of the system's for dates and entry times.
+/// - `record_utc_offset`: whether new entry headers record the UTC offset.
+///
+/// Call this once at startup, before `now` or `today` are first used.
+///
+/// # Arguments
+///
+/// * `config` - Application configuration
+///
+/// # Errors
+///
+/// Returns an error if `day_starts_at` is not in `HH:MM` form or `timezone`
+/// is not a known timezone.
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::config::load_config;
+/// use dailylog::date::{configure_clock, today};
+///
+/// configure_clock(&load_config()?)?;
+/// let date = today();
+/// ```
+pub fn configure_clock(config: &Config) -> anyhow::Result<()> {
+    let day_starts_at = match config.day_starts_at.as_deref() {
+        Some(spec) => NaiveTime::parse_from_str(spec.trim(), "%H:%M").map_err(|_| {
+            anyhow::anyhow!(
+                "Invalid day_starts_at '{}' in ~/.dailylog.toml: expected HH:MM, such as \"04:00\"",
+                spec
+            )
+        })?,
+        None => NaiveTime::MIN,
+    };
+    let timezone = match config.timezone.as_deref() {
+        Some(name) => Some(name.trim().parse::<Tz>().map_err(|_| {
+            anyhow::anyhow!(
+                "Unknown timezone '{}' in ~/.dailylog.toml: expected a name such as \"Europe/Berlin\" or \"UTC\"",
+                name
+            )
+        })?),
+        None => None,
+    };
+
+    CLOCK
+        .set(Clock {
+            day_starts_at,
+            timezone,
+            record_utc_offset: config.record_utc_offset.unwrap_or(false),
+        })
+        .ok();
+    Ok(())
+}
+
+/// Returns the current date and time in the configured timezone.
+pub fn now() -> DateTime<FixedOffset> {
+    match clock().timezone {
+        Some(timezone) => Utc::now().with_timezone(&timezone).fixed_offset(),
+        None => Local::now().fixed_offset(),
+    }
+}
+
+/// Returns `true` if new entry headers should record the UTC offset.
+pub fn records_utc_offset() -> bool {
+    clock().record_utc_offset
+}
+
+/// Returns how long after the start of the day (see `configure_clock`) `time` is.
+///
+/// Sorting entry times by this keeps a 1am entry after the previous
+/// evening's entries when the day starts at 4am.
+pub fn time_into_day(time: NaiveTime) -> Duration {
+    elapsed_since(clock().day_starts_at, time)
+}
+
+/// Returns how long after `day_starts_at` `time` is, wrapping past midnight.
+fn elapsed_since(day_starts_at: NaiveTime, time: NaiveTime) -> Duration {
+    let elapsed = time - day_starts_at;
+    if elapsed < Duration::zero() {
+        elapsed + Duration::days(1)
+    } else {
+        elapsed
+    }
+}
+
+/// Returns the UTC offset in the configured timezone at `time` on the log
+/// day `date`.
+///
+/// A time before the `day_starts_at` time (see `configure_clock`) falls on
+/// the next calendar day. Returns `None` if that local time does not exist,
+/// as when the clocks go forward; when it happens twice, the earlier offset
+/// is used.
+pub fn utc_offset_at(date: NaiveDate, time: NaiveTime) -> Option<FixedOffset> {
+    let day_starts_at = clock().day_starts_at;
+    let local = date
+        .and_time(day_starts_at)
+        .checked_add_signed(time_into_day(time))?;
+    match clock().timezone {
+        Some(timezone) => timezone
+            .from_local_datetime(&local)
+            .earliest()
+            .map(|dt| *dt.fixed_offset().offset()),
+        None => Local
+            .from_local_datetime(&local)
+            .earliest()
+            .map(|dt| *dt.fixed_offset().offset()),
+    }
+}
+
+/// Returns today's date in the configured timezone.
+///
+/// Before the `day_starts_at` time (see `configure_clock`), this is still
+/// the previous day.
+pub fn today() -> NaiveDate {
+    log_day(now().naive_local(), clock().day_starts_at)
+}
+
+/// Returns the log day that the local date and time `now` falls on when
+/// days start at `day_starts_at`.
+fn log_day(now: NaiveDateTime, day_starts_at: NaiveTime) -> NaiveDate {
+    (now - (day_starts_at - NaiveTime::MIN)).date()
+}
+
+/// Parses a day string into a `Weekday` enum.
+///
+/// Accepts both full day names and three-letter abbreviations,
+/// case-insensitive.
+///
+/// # Arguments
+///
+/// * `day_str` - The day string to parse (e.g., "monday", "Mon", "tue")
+///
+/// # Returns
+///
+/// `Some(Weekday)` if the string is recognized, `None` otherwise.
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::date::parse_weekday;
+/// use chrono::Weekday;
+///
+/// assert_eq!(parse_weekday("monday"), Some(Weekday::Mon));
+/// assert_eq!(parse_weekday("tue"), Some(Weekday::Tue));
+/// assert_eq!(parse_weekday("invalid"), None);
+/// ```
+pub fn parse_weekday(day_str: &str) -> Option<Weekday> {
+    match day_str.to_lowercase().as_str() {
+        "monday" | "mon" => Some(Weekday::Mon),
+        "tuesday" | "tue" => Some(Weekday::Tue),
+        "wednesday" | "wed" => Some(Weekday::Wed),
+        "thursday" | "thu" => Some(Weekday::Thu),
+        "friday" | "fri" => Some(Weekday::Fri),
+        "saturday" | "sat" => Some(Weekday::Sat),
+        "sunday" | "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Parses a date expression relative to `today`.
+///
+/// Accepted forms:
+/// - `YYYY-MM-DD`: an absolute date
+/// - `today`, `yesterday`
+/// - `-Nd` / `-Nw`: N days or weeks before today
+/// - `friday`, `fri`: the most recent Friday, including today
+/// - `last friday`: the most recent Friday strictly before today
+///
+/// # Arguments
+///
+/// * `spec` - The date expression to parse
+/// * `today` - The date that relative expressions are resolved against
+///
+/// # Errors
+///
+/// Returns an error if the expression is not recognized, or if it names a
+/// date outside the range chrono can represent.
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use dailylog::date::parse_date;
+///
+/// let today = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(); // a Monday
+/// assert_eq!(parse_date("-3d", today)?, NaiveDate::from_ymd_opt(2024, 1, 12).unwrap());
+/// assert_eq!(parse_date("last friday", today)?, NaiveDate::from_ymd_opt(2024, 1, 12).unwrap());
+/// ```
+pub fn parse_date(spec: &str, today: NaiveDate) -> anyhow::Result<NaiveDate> {
+    let spec = spec.trim().to_lowercase();
+
+    match spec.as_str() {
+        "today" => return Ok(today),
+        "yesterday" => return Ok(today - Duration::days(1)),
+        _ => {}
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(&spec, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    if let Some(offset) = spec.strip_prefix('-') {
+        if let Some(days) = offset.strip_suffix('d').and_then(|n| n.parse::<i64>().ok())
+            && let Some(date) = Duration::try_days(days).and_then(|delta| today.checked_sub_signed(delta))
+        {
+            return Ok(date);
+        }
+        if let Some(weeks) = offset.strip_suffix('w').and_then(|n| n.parse::<i64>().ok())
+            && let Some(date) = Duration::try_weeks(weeks).and_then(|delta| today.checked_sub_signed(delta))
+        {
+            return Ok(date);
+        }
+    }
+
+    let (strictly_before, day_name) = match spec.strip_prefix("last ") {
+        Some(rest) => (true, rest.trim()),
+        None => (false, spec.as_str()),
+    };
+    if let Some(weekday) = parse_weekday(day_name) {
+        return Ok(most_recent_weekday(today, weekday, strictly_before));
+    }
+
+    Err(anyhow::anyhow!(
+        "Invalid date '{}': expected YYYY-MM-DD, -Nd, -Nw, 'yesterday' or a weekday like 'last friday'",
+        spec
+    ))
+}
+
+/// Returns the day `days - 1` days before `end`, the start of a `days`-day range.
+fn days_before(end: NaiveDate, days: u32) -> anyhow::Result<NaiveDate> {
+    end.checked_sub_signed(Duration::days(days.saturating_sub(1) as i64))
+        .ok_or_else(|| anyhow::anyhow!("Invalid number of days {}: out of range", days))
+}
+
+/// Returns the most recent occurrence of `weekday` on or before `today`.
+///
+/// If `strictly_before` is set, today itself is never returned.
+fn most_recent_weekday(today: NaiveDate, weekday: Weekday, strictly_before: bool) -> NaiveDate {
+    let mut days_back = (7 + today.weekday().num_days_from_monday() as i64
+        - weekday.num_days_from_monday() as i64)
+        % 7;
+    if days_back == 0 && strictly_before {
+        days_back = 7;
+    }
+    today - Duration::days(days_back)
+}
+
+/// Resolves an optional date expression, falling back to `default` when absent.
+///
+/// # Errors
+///
+/// Returns an error if the expression is present but cannot be parsed.
+pub fn resolve_date(spec: Option<&str>, default: NaiveDate) -> anyhow::Result<NaiveDate> {
+    match spec {
+        Some(spec) => parse_date(spec, today()),
+        None => Ok(default),
+    }
+}
+
+/// An inclusive range of dates with a human-readable label.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateRange {
+    /// First day of the range
+    pub start: NaiveDate,
+    /// Last day of the range
+    pub end: NaiveDate,
+    /// Description used in headings, e.g. "Past 7 Days" or "Week of 2024-01-15"
+    pub label: String,
+}
+
+impl DateRange {
+    /// Creates a range from `start` to `end`, inclusive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `start` is after `end`.
+    pub fn new(start: NaiveDate, end: NaiveDate, label: String) -> anyhow::Result<Self> {
+        if start > end {
+            return Err(anyhow::anyhow!(
+                "Invalid date range: {} is after {}",
+                start.format("%Y-%m-%d"),
+                end.format("%Y-%m-%d")
+            ));
+        }
+        Ok(DateRange { start, end, label })
+    }
+
+    /// A range given as a single date expression or `FROM..TO`.
+    ///
+    /// Either side of `FROM..TO` accepts anything `parse_date` does, so
+    /// `-7d..yesterday` and `2024-01-01..2024-01-31` are both valid. A single
+    /// date gives a one-day range.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either expression is invalid or `FROM` is after `TO`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use dailylog::date::DateRange;
+    ///
+    /// let today = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+    /// let range = DateRange::parse("-2d..today", today)?;
+    /// assert_eq!(range.start, NaiveDate::from_ymd_opt(2024, 1, 13).unwrap());
+    /// ```
+    pub fn parse(spec: &str, today: NaiveDate) -> anyhow::Result<Self> {
+        match spec.split_once("..") {
+            Some((from, to)) => Self::between(Some(from), Some(to), 1, today),
+            None => Ok(Self::day(parse_date(spec, today)?)),
+        }
+    }
+
+    /// A single day.
+    pub fn day(date: NaiveDate) -> Self {
+        DateRange {
+            start: date,
+            end: date,
+            label: date.format("%Y-%m-%d").to_string(),
+        }
+    }
+
+    /// The last `days` days, ending today.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range would start before the earliest supported date.
+    pub fn last_days(days: u32, today: NaiveDate) -> anyhow::Result<Self> {
+        let start = days_before(today, days)?;
+        Self::new(start, today, format!("Past {} Days", days))
+    }
+
+    /// A range between two optional date expressions.
+    ///
+    /// A missing `from` defaults to `default_days` days before `to`, and a
+    /// missing `to` defaults to today.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either expression is invalid, `from` is after `to`,
+    /// or the default start is before the earliest supported date.
+    pub fn between(
+        from: Option<&str>,
+        to: Option<&str>,
+        default_days: u32,
+        today: NaiveDate,
+    ) -> anyhow::Result<Self> {
+        let end = match to {
+            Some(to) => parse_date(to, today)?,
+            None => today,
+        };
+        let start = match from {
+            Some(from) => parse_date(from, today)?,
+            None => days_before(end, default_days)?,
+        };
+        let label = format!("{} to {}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d"));
+        Self::new(start, end, label)
+    }
+
+    /// A calendar week, Monday to Sunday.
+    ///
+    /// `spec` may be an ISO week number in the current year (`42`), a number
+    /// of weeks ago (`-1` for last week), or `None` for the current week.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the week is not valid or out of range.
+    pub fn week(spec: Option<&str>, today: NaiveDate) -> anyhow::Result<Self> {
+        let iso = today.iso_week();
+        let monday = match spec.map(str::trim) {
+            None => today - Duration::days(today.weekday().num_days_from_monday() as i64),
+            Some(spec) if spec.starts_with('-') => {
+                let weeks_ago: i64 = spec[1..]
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid week '{}'", spec))?;
+                let this_monday =
+                    today - Duration::days(today.weekday().num_days_from_monday() as i64);
+                Duration::try_weeks(weeks_ago)
+                    .and_then(|delta| this_monday.checked_sub_signed(delta))
+                    .ok_or_else(|| anyhow::anyhow!("Invalid week '{}': out of range", spec))?
+            }
+            Some(spec) => {
+                let week: u32 = spec
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid week '{}'", spec))?;
+                NaiveDate::from_isoywd_opt(iso.year(), week, Weekday::Mon).ok_or_else(|| {
+                    anyhow::anyhow!("Week {} does not exist in {}", week, iso.year())
+                })?
+            }
+        };
+
+        let sunday = monday
+            .checked_add_signed(Duration::days(6))
+            .ok_or_else(|| anyhow::anyhow!("Invalid week: out of range"))?;
+        let label = format!("Week of {}", monday.format("%Y-%m-%d"));
+        Self::new(monday, sunday, label)
+    }
+
+    /// A calendar month.
+    ///
+    /// `spec` may be `YYYY-MM`, a number of months ago (`-1` for last month),
+    /// or `None` for the current month.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the month is not valid or out of range.
+    pub fn month(spec: Option<&str>, today: NaiveDate) -> anyhow::Result<Self> {
+        let first_of_month = today.with_day(1).unwrap_or(today);
+        let start = match spec.map(str::trim) {
+            None => first_of_month,
+            Some(spec) if spec.starts_with('-') => {
+                let months_ago: u32 = spec[1..]
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid month '{}'", spec))?;
+                first_of_month
+                    .checked_sub_months(Months::new(months_ago))
+                    .ok_or_else(|| anyhow::anyhow!("Invalid month '{}': out of range", spec))?
+            }
+            Some(spec) => NaiveDate::parse_from_str(&format!("{}-01", spec), "%Y-%m-%d")
+                .map_err(|_| anyhow::anyhow!("Invalid month '{}': expected YYYY-MM", spec))?,
+        };
+
+        let end = start
+            .checked_add_months(Months::new(1))
+            .and_then(|next| next.pred_opt())
+            .ok_or_else(|| anyhow::anyhow!("Invalid month '{}': out of range", start.format("%Y-%m")))?;
+        Self::new(start, end, start.format("%B %Y").to_string())
+    }
+
+    /// A calendar year, given as `YYYY` or `None` for the current year.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the year is not valid.
+    pub fn year(spec: Option<&str>, today: NaiveDate) -> anyhow::Result<Self> {
+        let year = match spec.map(str::trim) {
+            None => today.year(),
+            Some(spec) => spec
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid year '{}': expected YYYY", spec))?,
+        };
+        let start = NaiveDate::from_ymd_opt(year, 1, 1)
+            .ok_or_else(|| anyhow::anyhow!("Invalid year '{}'", year))?;
+        let end = NaiveDate::from_ymd_opt(year, 12, 31)
+            .ok_or_else(|| anyhow::anyhow!("Invalid year '{}'", year))?;
+        Self::new(start, end, year.to_string())
+    }
+
+    /// Iterates over the days in the range, from newest to oldest, stopping at `today`.
+    pub fn days_until(&self, today: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+        let start = self.start;
+        let end = self.end.min(today);
+        end.iter_days()
+            .rev()
+            .take_while(move |date| *date >= start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(spec: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(spec, "%Y-%m-%d").unwrap()
+    }
+
+    fn time(spec: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(spec, "%H:%M").unwrap()
+    }
+
+    /// A Monday
+    const TODAY: &str = "2024-01-15";
+
+    #[test]
+    fn parses_absolute_dates_and_words() {
+        let today = date(TODAY);
+        assert_eq!(parse_date("2023-12-31", today).unwrap(), date("2023-12-31"));
+        assert_eq!(parse_date(" Today ", today).unwrap(), today);
+        assert_eq!(parse_date("yesterday", today).unwrap(), date("2024-01-14"));
+    }
+
+    #[test]
+    fn parses_relative_offsets() {
+        let today = date(TODAY);
+        assert_eq!(parse_date("-0d", today).unwrap(), today);
+        assert_eq!(parse_date("-3d", today).unwrap(), date("2024-01-12"));
+        assert_eq!(parse_date("-20d", today).unwrap(), date("2023-12-26"));
+        assert_eq!(parse_date("-2w", today).unwrap(), date("2024-01-01"));
+        assert!(parse_date("-3", today).is_err());
+        assert!(parse_date("-xd", today).is_err());
+        assert!(parse_date("-99999999999d", today).is_err());
+    }
+
+    #[test]
+    fn parses_weekdays() {
+        let today = date(TODAY);
+        assert_eq!(parse_date("monday", today).unwrap(), today);
+        assert_eq!(
+            parse_date("last monday", today).unwrap(),
+            date("2024-01-08")
+        );
+        assert_eq!(parse_date("friday", today).unwrap(), date("2024-01-12"));
+        assert_eq!(parse_date("Last Fri", today).unwrap(), date("2024-01-12"));
+        assert_eq!(parse_date("sun", today).unwrap(), date("2024-01-14"));
+    }
+
+    #[test]
+    fn rejects_unknown_dates() {
+        let today = date(TODAY);
+        assert!(parse_date("someday", today).is_err());
+        assert!(parse_date("2024-02-30", today).is_err());
+        assert!(parse_date("last", today).is_err());
+        assert!(parse_date("next friday", today).is_err());
+    }
+
+    #[test]
+    fn parses_ranges() {
+        let today = date(TODAY);
+        let range = DateRange::parse("-7d..yesterday", today).unwrap();
+        assert_eq!(
+            (range.start, range.end),
+            (date("2024-01-08"), date("2024-01-14"))
+        );
+        assert_eq!(range.label, "2024-01-08 to 2024-01-14");
+
+        let range = DateRange::parse("last friday", today).unwrap();
+        assert_eq!(
+            (range.start, range.end),
+            (date("2024-01-12"), date("2024-01-12"))
+        );
+
+        assert!(DateRange::parse("today..-1d", today).is_err());
+        assert!(DateRange::parse("-1d..someday", today).is_err());
+    }
+
+    #[test]
+    fn finds_weeks() {
+        let today = date("2024-01-17");
+        let week = DateRange::week(None, today).unwrap();
+        assert_eq!(
+            (week.start, week.end),
+            (date("2024-01-15"), date("2024-01-21"))
+        );
+        assert_eq!(week.label, "Week of 2024-01-15");
+
+        let week = DateRange::week(Some("-1"), today).unwrap();
+        assert_eq!(
+            (week.start, week.end),
+            (date("2024-01-08"), date("2024-01-14"))
+        );
+
+        let week = DateRange::week(Some("1"), today).unwrap();
+        assert_eq!(
+            (week.start, week.end),
+            (date("2024-01-01"), date("2024-01-07"))
+        );
+
+        assert!(DateRange::week(Some("54"), today).is_err());
+        assert!(DateRange::week(Some("last"), today).is_err());
+    }
+
+    #[test]
+    fn finds_months() {
+        let today = date("2024-03-31");
+        let month = DateRange::month(None, today).unwrap();
+        assert_eq!(
+            (month.start, month.end),
+            (date("2024-03-01"), date("2024-03-31"))
+        );
+        assert_eq!(month.label, "March 2024");
+
+        let month = DateRange::month(Some("-1"), today).unwrap();
+        assert_eq!(
+            (month.start, month.end),
+            (date("2024-02-01"), date("2024-02-29"))
+        );
+
+        let month = DateRange::month(Some("2023-12"), today).unwrap();
+        assert_eq!(
+            (month.start, month.end),
+            (date("2023-12-01"), date("2023-12-31"))
+        );
+
+        assert!(DateRange::month(Some("2023-13"), today).is_err());
+    }
+
+    #[test]
+    fn day_rolls_over_at_day_starts_at() {
+        let starts_at = time("04:00");
+        let day = date(TODAY);
+        assert_eq!(
+            log_day(day.and_time(time("01:30")), starts_at),
+            date("2024-01-14")
+        );
+        assert_eq!(log_day(day.and_time(time("04:00")), starts_at), day);
+        assert_eq!(log_day(day.and_time(time("23:59")), starts_at), day);
+        assert_eq!(log_day(day.and_time(time("00:00")), NaiveTime::MIN), day);
+    }
+
+    #[test]
+    fn times_after_midnight_sort_after_the_evening() {
+        let starts_at = time("04:00");
+        assert_eq!(elapsed_since(starts_at, time("04:00")), Duration::zero());
+        assert_eq!(elapsed_since(starts_at, time("23:00")), Duration::hours(19));
+        assert_eq!(elapsed_since(starts_at, time("01:00")), Duration::hours(21));
+        assert!(elapsed_since(starts_at, time("01:00")) > elapsed_since(starts_at, time("23:00")));
+    }
+}
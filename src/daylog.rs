@@ -0,0 +1,564 @@
+//! Structured model of daily log files.
+//!
+//! A daily log file is a sequence of entries in the format written by
+//! [`format_entry`](crate::entry::format_entry):
+//!
+//! ```markdown
+//! ## 14:30 - Meeting notes
+//!
+//! Discussed project timeline.
+//!
+//! ## 16:00 +02:00 - Code review
+//!
+//! ```
+//!
+//! A header may record the UTC offset the entry was written at, after the
+//! time (see the `record_utc_offset` config option). Untitled entries have no
+//! header, so one that follows another entry starts with an
+//! [`UNTITLED_MARKER`] line, which markdown viewers hide.
+//!
+//! This module parses that format into [`DayLog`] and [`Entry`] values and
+//! serializes them back, so other modules can work with entries instead of
+//! re-scanning markdown. Parsing also records where each entry sits in the
+//! file (see [`Span`]), so a single entry can be changed without rewriting
+//! the rest of the file.
+
+use crate::entry::{extract_tags, get_log_file_path_for_date};
+use chrono::{FixedOffset, NaiveDate, NaiveTime};
+use std::fs;
+
+/// Line that starts an untitled entry following another entry.
+///
+/// Without it, the untitled entry's text would read as more of the previous
+/// entry's body.
+pub const UNTITLED_MARKER: &str = "<!-- untitled -->";
+
+/// A single journal entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// Time from the `## HH:MM - title` header, if the entry has one
+    pub time: Option<NaiveTime>,
+    /// UTC offset recorded after the time, as in `## HH:MM +02:00 - title`
+    pub offset: Option<FixedOffset>,
+    /// Entry title; `None` for untitled, body-only entries
+    pub title: Option<String>,
+    /// Entry body without leading or trailing blank lines
+    pub body: String,
+    /// Hashtags used in the title and body, lowercased and without `#`
+    pub tags: Vec<String>,
+}
+
+impl Entry {
+    /// Creates an entry, extracting its tags from the title and body.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time of the entry; only used when the entry has a title
+    /// * `title` - Optional title of the entry
+    /// * `body` - Body content of the entry
+    pub fn new(time: Option<NaiveTime>, title: Option<String>, body: String) -> Self {
+        let mut tags = title.as_deref().map(extract_tags).unwrap_or_default();
+        for tag in extract_tags(&body) {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+
+        Entry {
+            time,
+            offset: None,
+            title,
+            body,
+            tags,
+        }
+    }
+
+    /// Returns the entry with `offset` as the UTC offset recorded in its header.
+    pub fn with_offset(mut self, offset: Option<FixedOffset>) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Returns `true` if the entry is tagged with `tag` (with or without `#`).
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.trim_start_matches('#').to_lowercase();
+        self.tags.contains(&tag)
+    }
+
+    /// Returns the `## HH:MM - title` header line, if the entry is titled.
+    ///
+    /// If the entry has a UTC offset, it follows the time: `## HH:MM +02:00 - title`.
+    pub fn header(&self) -> Option<String> {
+        let title = self.title.as_deref().filter(|title| !title.is_empty())?;
+        let mut time = self
+            .time
+            .map(|time| time.format("%H:%M").to_string())
+            .unwrap_or_else(|| "00:00".to_string());
+        if let Some(offset) = self.offset {
+            time = format!("{} {}", time, offset);
+        }
+        Some(format!("## {} - {}", time, title))
+    }
+
+    /// Serializes the entry to markdown, exactly as `format_entry` writes it.
+    ///
+    /// Titled entries become a header followed by a blank line and the body;
+    /// untitled entries are just the body. Returns an empty string if the
+    /// entry has neither title nor body.
+    pub fn to_markdown(&self) -> String {
+        match self.header() {
+            Some(header) if self.body.is_empty() => format!("{}\n", header),
+            Some(header) => format!("{}\n\n{}\n", header, self.body),
+            None if self.body.is_empty() => String::new(),
+            None => format!("{}\n", self.body),
+        }
+    }
+
+    /// Returns a one-line description of the entry for messages: its
+    /// `HH:MM - title` header, or the first line of an untitled entry's body.
+    pub fn label(&self) -> String {
+        match self.header() {
+            Some(header) => header.trim_start_matches("## ").to_string(),
+            None => self.body.lines().next().unwrap_or_default().to_string(),
+        }
+    }
+
+    /// Counts the words in the entry's title and body.
+    pub fn word_count(&self) -> usize {
+        let title_words = self
+            .title
+            .as_deref()
+            .map(|title| title.split_whitespace().count())
+            .unwrap_or(0);
+        title_words + self.body.split_whitespace().count()
+    }
+}
+
+/// Where an entry sits in the content it was parsed from, as byte offsets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span {
+    /// Start of the entry's first line: its header, its untitled marker, or
+    /// the first line of its body
+    pub start: usize,
+    /// End of the entry's last non-blank line, after its line ending
+    pub text_end: usize,
+    /// Start of the next entry, or the end of the content; the blank lines
+    /// between `text_end` and here separate the entry from the next one
+    pub end: usize,
+}
+
+/// All entries logged on a single day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayLog {
+    /// The day the log belongs to
+    pub date: NaiveDate,
+    /// Entries in the order they appear in the file
+    pub entries: Vec<Entry>,
+}
+
+impl DayLog {
+    /// Parses the content of a daily log file.
+    ///
+    /// Each `## HH:MM - title` line starts a new titled entry, as does
+    /// `## HH:MM +02:00 - title` with a recorded UTC offset. Text before
+    /// the first header is an untitled entry, and an [`UNTITLED_MARKER`]
+    /// line starts another. Everything else, blank lines included, belongs
+    /// to the entry above it.
+    ///
+    /// Line endings (`\n` or `\r\n`) and trailing spaces after headers are
+    /// accepted. Serializing with [`DayLog::to_markdown`] gives back the same
+    /// entries, but in the `format_entry` format, so it only reproduces files
+    /// written in that format. To change one entry and leave the rest of a
+    /// file as it was, use the spans from [`DayLog::parse_with_spans`].
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The day the content belongs to
+    /// * `content` - The content of the daily log file
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use dailylog::daylog::DayLog;
+    ///
+    /// let content = "## 14:30 - Meeting notes\n\nDiscussed timeline.\n\n## 16:00 - Code review\n\n<!-- untitled -->\nUntitled note\n\n";
+    /// let day = DayLog::parse(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(), content);
+    /// assert_eq!(day.entries.len(), 3);
+    /// assert_eq!(day.to_markdown(), content);
+    /// ```
+    pub fn parse(date: NaiveDate, content: &str) -> Self {
+        Self::parse_with_spans(date, content).0
+    }
+
+    /// Parses the content of a daily log file as `parse` does, also returning
+    /// where each entry sits in `content`.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The day the content belongs to
+    /// * `content` - The content of the daily log file
+    ///
+    /// # Returns
+    ///
+    /// The day's log, and one [`Span`] per entry, in the same order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use dailylog::daylog::DayLog;
+    ///
+    /// let content = "## 09:00 - Standup\n\n## 14:30 - Code review\n\n";
+    /// let (_, spans) = DayLog::parse_with_spans(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(), content);
+    /// assert_eq!(&content[spans[1].start..spans[1].end], "## 14:30 - Code review\n\n");
+    /// ```
+    pub fn parse_with_spans(date: NaiveDate, content: &str) -> (Self, Vec<Span>) {
+        let mut parser = Parser::default();
+        let mut pos = 0;
+
+        for raw_line in content.split_inclusive('\n') {
+            let line = raw_line.strip_suffix('\n').unwrap_or(raw_line);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            let line_end = pos + raw_line.len();
+
+            if let Some((time, offset, title)) = parse_header(line) {
+                parser.finish_entry(pos);
+                parser.start_entry(pos, Some((Some(time), offset, Some(title))));
+                parser.text_end = line_end;
+            } else if line.trim() == UNTITLED_MARKER {
+                parser.finish_entry(pos);
+                parser.start_entry(pos, None);
+                parser.text_end = line_end;
+            } else if line.trim().is_empty() {
+                parser.body_lines.push(line);
+            } else {
+                if parser.start.is_none() {
+                    parser.start_entry(pos, None);
+                }
+                parser.body_lines.push(line);
+                parser.text_end = line_end;
+            }
+            pos = line_end;
+        }
+
+        parser.finish_entry(content.len());
+        (
+            DayLog {
+                date,
+                entries: parser.entries,
+            },
+            parser.spans,
+        )
+    }
+
+    /// Loads the log for `date` from `log_dir`.
+    ///
+    /// A missing file yields a log with no entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log file exists but cannot be read.
+    pub fn load(log_dir: &str, date: NaiveDate) -> anyhow::Result<Self> {
+        let path = get_log_file_path_for_date(log_dir, date);
+        if !path.exists() {
+            return Ok(DayLog {
+                date,
+                entries: Vec::new(),
+            });
+        }
+        Ok(Self::parse(date, &fs::read_to_string(path)?))
+    }
+
+    /// Returns `true` if the day has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns entry number `number` (starting at 1), in file order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the valid range if there is no such entry.
+    pub fn entry(&self, number: usize) -> anyhow::Result<&Entry> {
+        self.entries
+            .get(number.wrapping_sub(1))
+            .ok_or_else(|| match self.entries.len() {
+                0 => anyhow::anyhow!("Nothing is logged on {}", self.date.format("%Y-%m-%d")),
+                count => anyhow::anyhow!(
+                    "No entry {} on {}: expected a number from 1 to {}",
+                    number,
+                    self.date.format("%Y-%m-%d"),
+                    count
+                ),
+            })
+    }
+
+    /// Finds an entry by its number (starting at 1) or its `HH:MM` time.
+    ///
+    /// # Arguments
+    ///
+    /// * `selector` - An entry number such as `2`, or a time such as `14:30`
+    ///
+    /// # Returns
+    ///
+    /// The entry's index in `entries`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no entry matches, or if several entries share the
+    /// given time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use dailylog::daylog::DayLog;
+    ///
+    /// let content = "## 09:00 - Standup\n\n## 14:30 - Code review\n\n";
+    /// let day = DayLog::parse(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(), content);
+    /// assert_eq!(day.find_entry("2").unwrap(), 1);
+    /// assert_eq!(day.find_entry("14:30").unwrap(), 1);
+    /// ```
+    pub fn find_entry(&self, selector: &str) -> anyhow::Result<usize> {
+        let selector = selector.trim();
+        if let Ok(number) = selector.parse::<usize>() {
+            self.entry(number)?;
+            return Ok(number - 1);
+        }
+
+        let time = NaiveTime::parse_from_str(selector, "%H:%M").map_err(|_| {
+            anyhow::anyhow!(
+                "Invalid entry '{}': expected its number (counting from 1) or its time as HH:MM",
+                selector
+            )
+        })?;
+        let matches: Vec<usize> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.title.is_some() && entry.time == Some(time))
+            .map(|(i, _)| i)
+            .collect();
+
+        match matches.as_slice() {
+            [index] => Ok(*index),
+            [] => Err(anyhow::anyhow!(
+                "No entry at {} on {}",
+                time.format("%H:%M"),
+                self.date.format("%Y-%m-%d")
+            )),
+            _ => Err(anyhow::anyhow!(
+                "{} entries are at {} on {}; use the entry's number instead ({})",
+                matches.len(),
+                time.format("%H:%M"),
+                self.date.format("%Y-%m-%d"),
+                matches
+                    .iter()
+                    .map(|i| (i + 1).to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    /// Serializes the day back into the daily log file format.
+    ///
+    /// Each entry is written as by `format_entry`, followed by a blank line,
+    /// matching how `append_to_log` separates entries. An untitled entry after
+    /// another entry starts with an [`UNTITLED_MARKER`] line.
+    pub fn to_markdown(&self) -> String {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| match entry.title {
+                None if i > 0 => format!("{}\n{}\n", UNTITLED_MARKER, entry.to_markdown()),
+                _ => format!("{}\n", entry.to_markdown()),
+            })
+            .collect()
+    }
+}
+
+/// The time, UTC offset and title from an entry's header.
+type Header = (Option<NaiveTime>, Option<FixedOffset>, Option<String>);
+
+/// State while parsing a daily log file line by line.
+#[derive(Default)]
+struct Parser<'a> {
+    /// Entries parsed so far
+    entries: Vec<Entry>,
+    /// Spans of the entries parsed so far
+    spans: Vec<Span>,
+    /// Where the current entry starts, if one has started
+    start: Option<usize>,
+    /// The current entry's header, or `None` if it is untitled
+    header: Option<Header>,
+    /// End of the current entry's last non-blank line
+    text_end: usize,
+    /// The current entry's body lines so far
+    body_lines: Vec<&'a str>,
+}
+
+impl Parser<'_> {
+    /// Starts a new entry at byte offset `start`.
+    fn start_entry(&mut self, start: usize, header: Option<Header>) {
+        self.start = Some(start);
+        self.header = header;
+        self.body_lines.clear();
+    }
+
+    /// Ends the current entry where the next one starts, at byte offset
+    /// `end`, adding it unless it is an untitled entry with no text.
+    fn finish_entry(&mut self, end: usize) {
+        let Some(start) = self.start.take() else {
+            return;
+        };
+        let header = self.header.take();
+        if header.is_none() && is_blank(&self.body_lines) {
+            return;
+        }
+        let (time, offset, title) = header.unwrap_or((None, None, None));
+        self.entries
+            .push(Entry::new(time, title, join_body(&self.body_lines)).with_offset(offset));
+        self.spans.push(Span {
+            start,
+            text_end: self.text_end,
+            end,
+        });
+    }
+}
+
+/// Parses a `## HH:MM - title` or `## HH:MM +02:00 - title` header line
+/// into its time, UTC offset and title.
+fn parse_header(line: &str) -> Option<(NaiveTime, Option<FixedOffset>, String)> {
+    let rest = line.trim_end().strip_prefix("## ")?;
+    let (stamp, title) = rest.split_once(" - ")?;
+    let (time, offset) = match stamp.trim().split_once(' ') {
+        Some((time, offset)) => (time, Some(offset.trim().parse::<FixedOffset>().ok()?)),
+        None => (stamp.trim(), None),
+    };
+    let time = NaiveTime::parse_from_str(time, "%H:%M").ok()?;
+    let title = title.trim();
+    if title.is_empty() {
+        return None;
+    }
+    Some((time, offset, title.to_string()))
+}
+
+/// Returns `true` if every line is blank.
+fn is_blank(lines: &[&str]) -> bool {
+    lines.iter().all(|line| line.trim().is_empty())
+}
+
+/// Joins body lines, dropping leading and trailing blank lines.
+fn join_body(lines: &[&str]) -> String {
+    let start = lines.iter().position(|line| !line.trim().is_empty());
+    let end = lines.iter().rposition(|line| !line.trim().is_empty());
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].join("\n"),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(content: &str) -> (DayLog, Vec<Span>) {
+        DayLog::parse_with_spans(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(), content)
+    }
+
+    fn time(hour: u32, minute: u32) -> Option<NaiveTime> {
+        NaiveTime::from_hms_opt(hour, minute, 0)
+    }
+
+    #[test]
+    fn round_trips_the_format_entry_format() {
+        let content = "## 09:00 - Standup\n\n## 14:30 +02:00 - Review\n\nLooked at #rust code.\n\n<!-- untitled -->\nA note\n\n";
+        let (day, _) = day(content);
+        assert_eq!(day.entries.len(), 3);
+        assert_eq!(day.to_markdown(), content);
+    }
+
+    #[test]
+    fn round_trips_untitled_text_before_the_first_header() {
+        let content = "A note\n\n## 09:00 - Standup\n\n";
+        let (day, _) = day(content);
+        assert_eq!(day.entries[0].title, None);
+        assert_eq!(day.to_markdown(), content);
+    }
+
+    #[test]
+    fn parses_headers_with_and_without_an_offset() {
+        let (day, _) = day("## 09:00 - Plain\n\n## 14:30 -05:00 - Offset\n\n");
+        assert_eq!(day.entries[0].time, time(9, 0));
+        assert_eq!(day.entries[0].offset, None);
+        assert_eq!(day.entries[1].time, time(14, 30));
+        assert_eq!(day.entries[1].offset, FixedOffset::west_opt(5 * 3600));
+        assert_eq!(day.entries[1].title.as_deref(), Some("Offset"));
+    }
+
+    #[test]
+    fn rejects_malformed_headers() {
+        for line in ["## 9am - Title", "## 09:00 -", "## 09:00 +2h - Title", "# 09:00 - Title"] {
+            assert_eq!(parse_header(line), None, "{}", line);
+        }
+    }
+
+    #[test]
+    fn parses_crlf_and_trailing_spaces() {
+        let (day, spans) = day("## 09:00 - Standup  \r\n\r\nline a\r\n\r\n## 10:00 - Next\r\n");
+        assert_eq!(day.entries.len(), 2);
+        assert_eq!(day.entries[0].title.as_deref(), Some("Standup"));
+        assert_eq!(day.entries[0].body, "line a");
+        assert_eq!(spans[0], Span { start: 0, text_end: 32, end: 34 });
+        assert_eq!(spans[1].start, 34);
+    }
+
+    #[test]
+    fn keeps_runs_of_blank_lines_in_the_body() {
+        let content = "## 09:00 - Old style\n\nline a\n\n\n\nline b\n\n";
+        let (day, spans) = day(content);
+        assert_eq!(day.entries.len(), 1);
+        assert_eq!(day.entries[0].body, "line a\n\n\n\nline b");
+        assert_eq!(spans[0].end, content.len());
+        assert_eq!(day.to_markdown(), content);
+    }
+
+    #[test]
+    fn untitled_marker_starts_a_new_entry() {
+        let content = "## 09:00 - Standup\n\nbody\n\n<!-- untitled -->\nnote\n\n";
+        let (day, spans) = day(content);
+        assert_eq!(day.entries.len(), 2);
+        assert_eq!(day.entries[0].body, "body");
+        assert_eq!(day.entries[1].title, None);
+        assert_eq!(day.entries[1].body, "note");
+        assert_eq!(&content[spans[1].start..spans[1].text_end], "<!-- untitled -->\nnote\n");
+    }
+
+    #[test]
+    fn spans_cover_each_entry_and_the_blank_lines_after_it() {
+        let content = "\n## 09:00 - One\n\nbody\n\n\n## 10:00 - Two";
+        let (_, spans) = day(content);
+        assert_eq!(&content[spans[0].start..spans[0].text_end], "## 09:00 - One\n\nbody\n");
+        assert_eq!(&content[spans[0].text_end..spans[0].end], "\n\n");
+        assert_eq!(&content[spans[1].start..spans[1].end], "## 10:00 - Two");
+    }
+
+    #[test]
+    fn collects_tags_from_title_and_body() {
+        let (day, _) = day("## 09:00 - Fix #Bug\n\nSee #bug and #ops-2\n\n");
+        assert_eq!(day.entries[0].tags, vec!["bug", "ops-2"]);
+        assert!(day.entries[0].has_tag("#OPS-2"));
+    }
+
+    #[test]
+    fn finds_entries_by_number_or_time() {
+        let (day, _) = day("## 09:00 - One\n\n## 09:00 - Two\n\n## 10:00 - Three\n\n");
+        assert_eq!(day.find_entry("3").unwrap(), 2);
+        assert_eq!(day.find_entry("10:00").unwrap(), 2);
+        assert!(day.find_entry("09:00").is_err());
+        assert!(day.find_entry("4").is_err());
+        assert!(day.find_entry("0").is_err());
+    }
+}
@@ -4,25 +4,39 @@
 //! markdown rendering with syntax highlighting and styled display
 //! of log entries.
 
-use crate::entry::{get_previous_day_log_path, open_editor, append_to_log};
-use chrono::{Duration, Local};
-use std::fs;
-use std::io::Write;
-use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+use crate::{
+    date::DateRange,
+    daylog::DayLog,
+    draft::{Draft, DraftKind},
+    entry::{append_to_log, entry_help, get_log_file_path_for_date, open_editor},
+    markdown::render_markdown,
+    theme::{Theme, layered},
+};
+use chrono::NaiveDate;
+use regex::Regex;
+use terminal_size::{Width, terminal_size};
+use termcolor::{ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 /// Renders markdown content to the terminal with color highlighting.
 ///
-/// Provides syntax highlighting for various markdown elements:
+/// The content is parsed as CommonMark and word-wrapped to the terminal
+/// width. Highlighting includes:
 /// - H1 headers: bright blue and bold
-/// - H2 headers: cyan and bold  
+/// - H2 headers: cyan and bold
 /// - H3 headers: green and bold
-/// - List items: yellow bullets
-/// - Code blocks: gray background
-/// - Bold text: terminal bold formatting
+/// - List items: yellow bullets or numbers, with nesting and task checkboxes
+/// - Code blocks and inline code: gray background
+/// - Bold, italic and struck-through text
+/// - Links: underlined, followed by their target
+/// - Blockquotes: a dimmed bar down the left side
+///
+/// When color is off, the output is plain text with the same layout.
 ///
 /// # Arguments
 ///
+/// * `out` - Where to write the rendered markdown
 /// * `content` - The markdown content to render
+/// * `theme` - The styles to use
 ///
 /// # Errors
 ///
@@ -32,85 +46,79 @@ use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 ///
 /// ```rust
 /// use dailylog::display::render_markdown_to_terminal;
+/// use dailylog::theme::Theme;
+/// use termcolor::{ColorChoice, StandardStream};
 ///
 /// let markdown = "# Title\n\n- List item\n- Another item";
-/// render_markdown_to_terminal(markdown)?;
+/// let mut out = StandardStream::stdout(ColorChoice::Auto);
+/// render_markdown_to_terminal(&mut out, markdown, &Theme::default())?;
 /// ```
-pub fn render_markdown_to_terminal(content: &str) -> anyhow::Result<()> {
-    let mut stdout = StandardStream::stdout(ColorChoice::Auto);
-
-    for line in content.lines() {
-        if line.starts_with("# ") {
-            // H1 headers - bright blue and bold
-            stdout.set_color(ColorSpec::new().set_fg(Some(Color::Blue)).set_bold(true))?;
-            writeln!(stdout, "{}", line)?;
-            stdout.reset()?;
-        } else if line.starts_with("## ") {
-            // H2 headers - cyan and bold
-            stdout.set_color(ColorSpec::new().set_fg(Some(Color::Cyan)).set_bold(true))?;
-            writeln!(stdout, "{}", line)?;
-            stdout.reset()?;
-        } else if line.starts_with("### ") {
-            // H3 headers - green and bold
-            stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)).set_bold(true))?;
-            writeln!(stdout, "{}", line)?;
-            stdout.reset()?;
-        } else if line.starts_with("- ") || line.starts_with("* ") {
-            // List items - yellow bullet
-            stdout.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))?;
-            write!(stdout, "• ")?;
-            stdout.reset()?;
-            writeln!(stdout, "{}", &line[2..])?;
-        } else if line.starts_with("```") {
-            // Code blocks - gray background
-            stdout.set_color(
-                ColorSpec::new()
-                    .set_bg(Some(Color::Black))
-                    .set_fg(Some(Color::White)),
-            )?;
-            writeln!(stdout, "{}", line)?;
-            stdout.reset()?;
-        } else if line.trim().is_empty() {
-            // Empty lines
-            writeln!(stdout)?;
-        } else {
-            // Regular text - check for inline formatting
-            let mut processed_line = line.to_string();
-
-            // Handle **bold** text
-            while let Some(start) = processed_line.find("**") {
-                if let Some(end) = processed_line[start + 2..].find("**") {
-                    let end = end + start + 2;
-                    let before = &processed_line[..start];
-                    let bold_text = &processed_line[start + 2..end];
-                    let after = &processed_line[end + 2..];
-
-                    write!(stdout, "{}", before)?;
-                    stdout.set_color(ColorSpec::new().set_bold(true))?;
-                    write!(stdout, "{}", bold_text)?;
-                    stdout.reset()?;
-                    processed_line = after.to_string();
-                } else {
-                    break;
-                }
-            }
-            writeln!(stdout, "{}", processed_line)?;
+pub fn render_markdown_to_terminal(
+    out: &mut impl WriteColor,
+    content: &str,
+    theme: &Theme,
+) -> anyhow::Result<()> {
+    let width = terminal_size().map(|(Width(width), _)| width as usize);
+    render_markdown(out, content, width, theme)
+}
+
+/// Writes a line of text with every match of `pattern` highlighted.
+///
+/// Non-matching text is written using `base` (pass `ColorSpec::new()` for
+/// plain text), while matches are layered with `highlight` (bold red in the
+/// default theme), like `grep --color`.
+///
+/// # Arguments
+///
+/// * `out` - The stream to write to
+/// * `text` - The text to write (without a trailing newline)
+/// * `pattern` - The pattern whose matches should be highlighted
+/// * `base` - The color specification for non-matching text
+/// * `highlight` - The style applied on top of `base` for matches
+///
+/// # Errors
+///
+/// Returns an error if writing to the terminal fails.
+pub fn write_highlighted(
+    out: &mut impl WriteColor,
+    text: &str,
+    pattern: &Regex,
+    base: &ColorSpec,
+    highlight: &ColorSpec,
+) -> anyhow::Result<()> {
+    let highlight = layered(base, highlight);
+
+    let mut last_end = 0;
+    for found in pattern.find_iter(text) {
+        if found.start() == found.end() {
+            continue;
         }
+        out.set_color(base)?;
+        write!(out, "{}", &text[last_end..found.start()])?;
+        out.set_color(&highlight)?;
+        write!(out, "{}", found.as_str())?;
+        last_end = found.end();
     }
+    out.set_color(base)?;
+    writeln!(out, "{}", &text[last_end..])?;
+    out.reset()?;
 
     Ok(())
 }
 
-/// Displays the previous day's log entry with colorized output.
+/// Displays a day's log entry with colorized output.
 ///
-/// Reads and displays yesterday's log file with:
+/// Reads and displays the log file for the given date with:
 /// - Styled header and footer showing the date
 /// - Markdown rendering with syntax highlighting
 /// - Appropriate messages if the file doesn't exist or is empty
 ///
 /// # Arguments
 ///
+/// * `out` - Where to write the log
 /// * `log_dir` - The directory containing log files
+/// * `date` - The date of the log to display
+/// * `theme` - The styles to use
 ///
 /// # Errors
 ///
@@ -121,54 +129,136 @@ pub fn render_markdown_to_terminal(content: &str) -> anyhow::Result<()> {
 /// # Example
 ///
 /// ```rust
-/// use dailylog::display::view_previous_day_log;
+/// use dailylog::display::view_day_log;
+/// use dailylog::theme::Theme;
+/// use chrono::NaiveDate;
+/// use termcolor::{ColorChoice, StandardStream};
 ///
-/// view_previous_day_log("/path/to/logs")?;
+/// let mut out = StandardStream::stdout(ColorChoice::Auto);
+/// let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+/// view_day_log(&mut out, "/path/to/logs", date, &Theme::default())?;
 /// ```
-pub fn view_previous_day_log(log_dir: &str) -> anyhow::Result<()> {
-    let log_path = get_previous_day_log_path(log_dir);
+pub fn view_day_log(
+    out: &mut impl WriteColor,
+    log_dir: &str,
+    date: NaiveDate,
+    theme: &Theme,
+) -> anyhow::Result<()> {
+    let log_path = get_log_file_path_for_date(log_dir, date);
+    let date_str = date.format("%Y-%m-%d").to_string();
 
     if !log_path.exists() {
-        println!("No log entry found for previous day: {:?}", log_path);
+        writeln!(out, "No log entry found for {}: {:?}", date_str, log_path)?;
         return Ok(());
     }
 
-    let content = fs::read_to_string(&log_path)?;
-    if content.trim().is_empty() {
-        println!("Previous day's log is empty: {:?}", log_path);
+    let day = DayLog::load(log_dir, date)?;
+    if day.is_empty() {
+        writeln!(out, "Log for {} is empty: {:?}", date_str, log_path)?;
     } else {
-        let yesterday = Local::now() - Duration::days(1);
-        let date_str = yesterday.format("%Y-%m-%d").to_string();
-
-        // Print header with styling
-        let mut stdout = StandardStream::stdout(ColorChoice::Auto);
-        stdout.set_color(ColorSpec::new().set_fg(Some(Color::Magenta)).set_bold(true))?;
-        writeln!(stdout, "=== Log entry for {} ===", date_str)?;
-        stdout.reset()?;
-
-        // Render the content with markdown styling
-        render_markdown_to_terminal(&content)?;
-
-        // Print footer with styling
-        stdout.set_color(ColorSpec::new().set_fg(Some(Color::Magenta)).set_bold(true))?;
-        writeln!(stdout, "=== End of log entry ===")?;
-        stdout.reset()?;
+        print_framed_day(out, &day, "End of log entry", theme)?;
     }
 
     Ok(())
 }
 
-/// Adds a new entry to the previous day's log file.
+/// Displays the logs for every day in a range, oldest first.
+///
+/// Each day is rendered with the same header and footer as `view_day_log`.
+/// Days without a log file, or whose log is empty, are skipped.
+///
+/// # Arguments
+///
+/// * `out` - Where to write the logs
+/// * `log_dir` - The directory containing log files
+/// * `range` - The days to display
+/// * `theme` - The styles to use
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - A log file cannot be read
+/// - Terminal output fails
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::date::{today, DateRange};
+/// use dailylog::display::show_logs;
+/// use dailylog::theme::Theme;
+/// use termcolor::{ColorChoice, StandardStream};
+///
+/// let mut out = StandardStream::stdout(ColorChoice::Auto);
+/// let range = DateRange::last_days(7, today())?;
+/// show_logs(&mut out, "/path/to/logs", &range, &Theme::default())?;
+/// ```
+pub fn show_logs(
+    out: &mut impl WriteColor,
+    log_dir: &str,
+    range: &DateRange,
+    theme: &Theme,
+) -> anyhow::Result<()> {
+    let mut days_shown = 0;
+
+    for date in range.start.iter_days().take_while(|date| *date <= range.end) {
+        let day = DayLog::load(log_dir, date)?;
+        if day.is_empty() {
+            continue;
+        }
+        if days_shown > 0 {
+            writeln!(out)?;
+        }
+        print_framed_day(out, &day, "End of log entry", theme)?;
+        days_shown += 1;
+    }
+
+    if days_shown == 0 {
+        writeln!(out, "No log entries found for {}", range.label)?;
+    }
+
+    Ok(())
+}
+
+/// Renders a day's log between "Log entry for" header and `footer` lines.
+fn print_framed_day(
+    out: &mut impl WriteColor,
+    day: &DayLog,
+    footer: &str,
+    theme: &Theme,
+) -> anyhow::Result<()> {
+    // Print header with styling
+    out.set_color(&theme.date_header)?;
+    writeln!(out, "=== Log entry for {} ===", day.date.format("%Y-%m-%d"))?;
+    out.reset()?;
+
+    // Render the content with markdown styling
+    render_markdown_to_terminal(out, &day.to_markdown(), theme)?;
+
+    // Print footer with styling
+    out.set_color(&theme.date_header)?;
+    writeln!(out, "=== {} ===", footer)?;
+    out.reset()?;
+
+    Ok(())
+}
+
+/// Adds a new entry to a past day's log file.
 ///
 /// This function:
-/// 1. Shows existing content from yesterday's log (if any) with colorized display
-/// 2. Opens the user's editor to write a new entry
-/// 3. Appends the new entry to yesterday's log file
+/// 1. Shows existing content from that day's log (if any) with colorized display
+/// 2. Opens the user's editor to write a new entry, starting from `template` if given
+/// 3. Appends the new entry to that day's log file
 /// 4. Provides appropriate feedback about the operation
 ///
 /// # Arguments
 ///
 /// * `log_dir` - The directory containing log files
+/// * `date` - The date of the log to append to
+/// * `theme` - The styles for showing the existing log
+/// * `color` - Whether to use colors when showing the existing log
+/// * `template` - An expanded entry template to pre-fill the editor with
+/// * `comment_char` - The prefix of help lines in the editor
+/// * `editor` - The `editor` config option, if set
 ///
 /// # Errors
 ///
@@ -181,51 +271,53 @@ pub fn view_previous_day_log(log_dir: &str) -> anyhow::Result<()> {
 /// # Example
 ///
 /// ```rust
-/// use dailylog::display::add_to_previous_day_log;
+/// use dailylog::display::add_to_day_log;
+/// use dailylog::theme::Theme;
+/// use chrono::NaiveDate;
+/// use termcolor::ColorChoice;
 ///
-/// add_to_previous_day_log("/path/to/logs")?;
+/// let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+/// add_to_day_log("/path/to/logs", date, &Theme::default(), ColorChoice::Auto, None, "#", None)?;
 /// ```
-pub fn add_to_previous_day_log(log_dir: &str) -> anyhow::Result<()> {
-    let log_path = get_previous_day_log_path(log_dir);
-    let yesterday = Local::now() - Duration::days(1);
-    let date_str = yesterday.format("%Y-%m-%d").to_string();
+pub fn add_to_day_log(
+    log_dir: &str,
+    date: NaiveDate,
+    theme: &Theme,
+    color: ColorChoice,
+    template: Option<&str>,
+    comment_char: &str,
+    editor: Option<&str>,
+) -> anyhow::Result<()> {
+    let log_path = get_log_file_path_for_date(log_dir, date);
+    let date_str = date.format("%Y-%m-%d").to_string();
 
     // Show existing content if available
     if log_path.exists() {
-        let content = fs::read_to_string(&log_path)?;
-        if !content.trim().is_empty() {
+        let day = DayLog::load(log_dir, date)?;
+        if !day.is_empty() {
             println!("Existing entry for {}:", date_str);
+            let mut stdout = StandardStream::stdout(color);
+            print_framed_day(&mut stdout, &day, "End of existing entry", theme)?;
 
-            // Print header with styling
-            let mut stdout = StandardStream::stdout(ColorChoice::Auto);
-            stdout.set_color(ColorSpec::new().set_fg(Some(Color::Magenta)).set_bold(true))?;
-            writeln!(stdout, "=== Log entry for {} ===", date_str)?;
-            stdout.reset()?;
-
-            // Render the content with markdown styling
-            render_markdown_to_terminal(&content)?;
-
-            // Print footer with styling
-            stdout.set_color(ColorSpec::new().set_fg(Some(Color::Magenta)).set_bold(true))?;
-            writeln!(stdout, "=== End of existing entry ===")?;
-            stdout.reset()?;
-
-            println!("\nAppending to yesterday's log...");
+            println!("\nAppending to log for {}...", date_str);
         } else {
-            println!("Creating new entry for yesterday ({})", date_str);
+            println!("Creating new entry for {}", date_str);
         }
     } else {
-        println!("Creating new entry for yesterday ({})", date_str);
+        println!("Creating new entry for {}", date_str);
     }
 
     // Open editor for new content
-    let entry = open_editor()?;
+    let draft = Draft::create(DraftKind::Entry, date)?;
+    let help = entry_help(log_dir, date, comment_char)?;
+    let entry = open_editor(&draft, template, &help, editor)?;
     if !entry.trim().is_empty() {
-        append_to_log(&log_path, &entry)?;
+        draft.save_with(|| append_to_log(&log_path, &entry))?;
         println!("Log saved to {:?}", log_path);
     } else {
+        draft.discard()?;
         println!("No content written. Aborted.");
     }
 
     Ok(())
-}
\ No newline at end of file
+}
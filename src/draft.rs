@@ -0,0 +1,506 @@
+//! Private editor files and draft recovery.
+//!
+//! Every editor session gets its own file in a private drafts directory
+//! (`~/.local/share/dailylog/drafts` on Linux), readable only by the current
+//! user. The file is deleted once its content has been saved to the log. If
+//! saving fails, or dailylog or the terminal dies while the editor is open,
+//! the file is left behind as a draft that `dailylog drafts` can restore.
+//!
+//! A draft of an edited entry also keeps the entry as it was, in a `.orig`
+//! file beside it, so restoring it replaces that entry rather than adding a
+//! copy.
+
+use crate::{
+    daylog::DayLog,
+    entry::{
+        append_to_log, get_log_file_path_for_date, read_log_file, replace_entry, strip_help,
+        write_day_log,
+    },
+    lock::lock_log_file,
+    theme::Theme,
+};
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use dirs::data_local_dir;
+use regex::Regex;
+use std::{
+    env,
+    fs::{self, DirBuilder, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    process,
+    sync::LazyLock,
+};
+use termcolor::WriteColor;
+
+#[cfg(unix)]
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+
+/// Matches draft file names: `{kind}_{date}_{created}_{pid}.md`.
+static DRAFT_NAME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(entry|edit|entry-edit)_(\d{4}-\d{2}-\d{2})_(\d{8}T\d{6})_(\d+)(?:_\d+)?\.md$")
+        .expect("valid draft name pattern")
+});
+
+/// Format of the creation time in draft file names.
+const CREATED_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// What an editor session was writing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DraftKind {
+    /// A new entry to append to a day's log
+    Entry,
+    /// The whole content of a day's log, being edited in place
+    Edit,
+    /// A single entry of a day's log, being edited in place
+    EntryEdit,
+}
+
+impl DraftKind {
+    /// The name used for this kind in draft file names.
+    fn name(self) -> &'static str {
+        match self {
+            DraftKind::Entry => "entry",
+            DraftKind::Edit => "edit",
+            DraftKind::EntryEdit => "entry-edit",
+        }
+    }
+}
+
+/// An editor session's file, kept until its content has been saved.
+#[derive(Debug)]
+pub struct Draft {
+    /// Path of the draft file
+    pub path: PathBuf,
+    /// Whether the draft is a new entry or an edited day log
+    pub kind: DraftKind,
+    /// The day the draft belongs to
+    pub date: NaiveDate,
+    /// When the editor session started
+    pub created: NaiveDateTime,
+    /// The process that started the editor session
+    pub pid: u32,
+}
+
+/// Returns the private directory that holds drafts.
+///
+/// Uses the platform's local data directory (`~/.local/share/dailylog/drafts`
+/// on Linux), falling back to the system temp directory.
+pub fn drafts_dir() -> PathBuf {
+    data_local_dir()
+        .unwrap_or_else(env::temp_dir)
+        .join("dailylog")
+        .join("drafts")
+}
+
+impl Draft {
+    /// Creates a new, uniquely named draft file that only the current user can read.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - Whether the session writes a new entry or edits a day log
+    /// * `date` - The day the session writes to
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the drafts directory or the file cannot be created.
+    pub fn create(kind: DraftKind, date: NaiveDate) -> anyhow::Result<Draft> {
+        let dir = drafts_dir();
+        let mut builder = DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        builder.mode(0o700);
+        builder
+            .create(&dir)
+            .map_err(|e| anyhow::anyhow!("Failed to create drafts directory {:?}: {}", dir, e))?;
+
+        let created = Local::now().naive_local();
+        let stem = format!(
+            "{}_{}_{}_{}",
+            kind.name(),
+            date.format("%Y-%m-%d"),
+            created.format(CREATED_FORMAT),
+            process::id()
+        );
+
+        // create_new fails rather than reuse a file another session owns
+        for attempt in 0.. {
+            let name = if attempt == 0 {
+                format!("{}.md", stem)
+            } else {
+                format!("{}_{}.md", stem, attempt)
+            };
+            let path = dir.join(name);
+
+            let mut options = OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            options.mode(0o600);
+
+            match options.open(&path) {
+                Ok(_) => {
+                    return Ok(Draft {
+                        path,
+                        kind,
+                        date,
+                        created,
+                        pid: process::id(),
+                    });
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => {
+                    return Err(anyhow::anyhow!("Failed to create draft {:?}: {}", path, e));
+                }
+            }
+        }
+        unreachable!("attempts are unbounded")
+    }
+
+    /// Reads a draft's details back from its file name.
+    fn from_path(path: PathBuf) -> Option<Draft> {
+        let name = path.file_name()?.to_str()?;
+        let captures = DRAFT_NAME.captures(name)?;
+        let kind = match &captures[1] {
+            "entry" => DraftKind::Entry,
+            "edit" => DraftKind::Edit,
+            _ => DraftKind::EntryEdit,
+        };
+        let date = NaiveDate::parse_from_str(&captures[2], "%Y-%m-%d").ok()?;
+        let created = NaiveDateTime::parse_from_str(&captures[3], CREATED_FORMAT).ok()?;
+        let pid = captures[4].parse().ok()?;
+        Some(Draft {
+            path,
+            kind,
+            date,
+            created,
+            pid,
+        })
+    }
+
+    /// Returns `true` if the session that started the draft is still running,
+    /// so its editor may still have the file open.
+    pub fn in_use(&self) -> bool {
+        self.pid != process::id() && process_is_running(self.pid)
+    }
+
+    /// Deletes the draft file, and the entry kept by `write_original` if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file exists but cannot be removed.
+    pub fn discard(&self) -> anyhow::Result<()> {
+        for path in [&self.path, &self.original_path()] {
+            match fs::remove_file(path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Path of the file holding the entry an `EntryEdit` draft replaces.
+    fn original_path(&self) -> PathBuf {
+        self.path.with_extension("orig")
+    }
+
+    /// Keeps the entry being edited, so the draft can replace it when restored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn write_original(&self, content: &str) -> anyhow::Result<()> {
+        let path = self.original_path();
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        options
+            .open(&path)
+            .and_then(|mut file| file.write_all(content.as_bytes()))
+            .map_err(|e| anyhow::anyhow!("Failed to write draft {:?}: {}", path, e))
+    }
+
+    /// Returns the entry kept by `write_original`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    pub fn read_original(&self) -> anyhow::Result<String> {
+        let path = self.original_path();
+        fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("Failed to read draft {:?}: {}", path, e))
+    }
+
+    /// Runs `save` and deletes the draft if it succeeds.
+    ///
+    /// If saving fails the draft is kept, and the returned error says how to
+    /// restore it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from `save`, or an error if the draft cannot be removed.
+    pub fn save_with(self, save: impl FnOnce() -> anyhow::Result<()>) -> anyhow::Result<()> {
+        match save() {
+            Ok(()) => self.discard(),
+            Err(e) => Err(anyhow::anyhow!(
+                "{}\nYour text was kept as a draft in {:?}. Run `dailylog drafts` to restore it.",
+                e,
+                self.path
+            )),
+        }
+    }
+
+    /// Replaces the draft's content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the draft file cannot be written.
+    pub fn write(&self, content: &str) -> anyhow::Result<()> {
+        fs::write(&self.path, content)
+            .map_err(|e| anyhow::anyhow!("Failed to write draft {:?}: {}", self.path, e))
+    }
+
+    /// Returns the draft's content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the draft file cannot be read.
+    pub fn read(&self) -> anyhow::Result<String> {
+        fs::read_to_string(&self.path)
+            .map_err(|e| anyhow::anyhow!("Failed to read draft {:?}: {}", self.path, e))
+    }
+}
+
+/// Returns `true` if a process with id `pid` is running.
+#[cfg(unix)]
+fn process_is_running(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    if pid <= 0 {
+        return false;
+    }
+    // Signal 0 only checks that the process exists and may be signalled
+    // SAFETY: kill with signal 0 sends nothing and has no side effects
+    if unsafe { libc::kill(pid, 0) } == 0 {
+        return true;
+    }
+    // EPERM: it exists, but belongs to another user
+    io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Returns `true` if a process with id `pid` is running.
+///
+/// There is no cheap check outside Unix, so drafts are never treated as in use.
+#[cfg(not(unix))]
+fn process_is_running(_pid: u32) -> bool {
+    false
+}
+
+/// Lists all drafts, most recent first.
+///
+/// # Errors
+///
+/// Returns an error if the drafts directory exists but cannot be read.
+pub fn list_drafts() -> anyhow::Result<Vec<Draft>> {
+    let dir = drafts_dir();
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut drafts = Vec::new();
+    for dir_entry in fs::read_dir(dir)? {
+        if let Some(draft) = Draft::from_path(dir_entry?.path()) {
+            drafts.push(draft);
+        }
+    }
+
+    drafts.sort_by(|a, b| b.created.cmp(&a.created).then_with(|| b.path.cmp(&a.path)));
+    Ok(drafts)
+}
+
+/// Returns draft number `number` (starting at 1) as listed by `show_drafts`.
+///
+/// Refuses drafts whose editor session is still running, since their
+/// content may be half-written.
+fn find_draft(number: usize) -> anyhow::Result<Draft> {
+    let drafts = list_drafts()?;
+    let count = drafts.len();
+    let draft = drafts
+        .into_iter()
+        .nth(number.wrapping_sub(1))
+        .ok_or_else(|| match count {
+            0 => anyhow::anyhow!("There are no drafts"),
+            _ => anyhow::anyhow!("No draft {}: expected a number from 1 to {}", number, count),
+        })?;
+    if draft.in_use() {
+        return Err(anyhow::anyhow!(
+            "Draft {} is still open in an editor (dailylog process {}). Finish that session first.",
+            number,
+            draft.pid
+        ));
+    }
+    Ok(draft)
+}
+
+/// Displays every draft with a number, its day and a preview of its content.
+///
+/// # Arguments
+///
+/// * `out` - Where to write the list
+/// * `theme` - The styles to use
+///
+/// # Errors
+///
+/// Returns an error if the drafts cannot be read or terminal output fails.
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::draft::show_drafts;
+/// use dailylog::theme::Theme;
+/// use termcolor::{ColorChoice, StandardStream};
+///
+/// let mut out = StandardStream::stdout(ColorChoice::Auto);
+/// show_drafts(&mut out, &Theme::default())?;
+/// ```
+pub fn show_drafts(out: &mut impl WriteColor, theme: &Theme) -> anyhow::Result<()> {
+    let drafts = list_drafts()?;
+    if drafts.is_empty() {
+        writeln!(out, "No drafts.")?;
+        return Ok(());
+    }
+
+    out.set_color(&theme.header)?;
+    writeln!(out, "=== Drafts ===")?;
+    out.reset()?;
+
+    for (i, draft) in drafts.iter().enumerate() {
+        let description = match draft.kind {
+            DraftKind::Entry => "new entry for",
+            DraftKind::Edit => "edit of",
+            DraftKind::EntryEdit => "entry edit for",
+        };
+        let content = strip_help(&draft.read()?);
+        let preview = content
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(|line| line.chars().take(50).collect::<String>())
+            .unwrap_or_else(|| "(empty)".to_string());
+
+        write!(out, "{:>3}. ", i + 1)?;
+        out.set_color(&theme.date_header)?;
+        write!(out, "{} {}", description, draft.date.format("%Y-%m-%d"))?;
+        out.reset()?;
+        write!(out, "  {}", preview)?;
+        out.set_color(&theme.muted)?;
+        write!(out, "  (started {})", draft.created.format("%Y-%m-%d %H:%M"))?;
+        if draft.in_use() {
+            write!(out, " (in use)")?;
+        }
+        writeln!(out)?;
+        out.reset()?;
+    }
+
+    writeln!(out)?;
+    writeln!(out, "Restore one with `dailylog drafts restore N` or delete it with `dailylog drafts discard N`.")?;
+    Ok(())
+}
+
+/// Saves a draft to its day's log and deletes it.
+///
+/// A new-entry draft is appended to the day's log like any other entry,
+/// without the editor help. An edit draft replaces the day's log, and an
+/// entry-edit draft replaces the entry it was edited from; either way the
+/// log's current version is moved to the trash.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory containing log files
+/// * `number` - The draft's number in the `dailylog drafts` list
+///
+/// # Returns
+///
+/// The path of the log file the draft was saved to.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - There is no draft with that number, or it is still open in an editor
+/// - The draft is empty
+/// - An entry-edit draft has a blank title, or its entry is no longer in the log
+/// - The draft or log file cannot be read or written
+pub fn restore_draft(log_dir: &str, number: usize) -> anyhow::Result<PathBuf> {
+    let draft = find_draft(number)?;
+    let log_path = get_log_file_path_for_date(log_dir, draft.date);
+    let content = draft.read()?;
+
+    match draft.kind {
+        DraftKind::Entry => {
+            let entry = strip_help(&content);
+            if entry.trim().is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Draft {} is empty. Delete it with `dailylog drafts discard {}`.",
+                    number,
+                    number
+                ));
+            }
+            append_to_log(&log_path, &entry)?;
+        }
+        DraftKind::Edit => {
+            if content.trim().is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Draft {} is empty. Delete it with `dailylog drafts discard {}`.",
+                    number,
+                    number
+                ));
+            }
+            let _lock = lock_log_file(&log_path)?;
+            let current = read_log_file(&log_path)?;
+            write_day_log(log_dir, draft.date, &current, &content)?;
+        }
+        DraftKind::EntryEdit => {
+            let original = DayLog::parse(draft.date, &draft.read_original()?)
+                .entries
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("Draft {} does not say which entry it edits", number))?;
+            replace_entry(log_dir, draft.date, &original, &content)?;
+        }
+    }
+
+    draft.discard()?;
+    Ok(log_path)
+}
+
+/// Deletes a draft, or every draft when `number` is `None`.
+///
+/// Drafts still open in an editor are left alone: asking for one by number
+/// is an error, and deleting every draft skips them.
+///
+/// # Arguments
+///
+/// * `number` - The draft's number in the `dailylog drafts` list
+///
+/// # Returns
+///
+/// The number of drafts deleted, and the number skipped because they are in use.
+///
+/// # Errors
+///
+/// Returns an error if there is no draft with that number, it is in use, or
+/// it cannot be deleted.
+pub fn discard_drafts(number: Option<usize>) -> anyhow::Result<(usize, usize)> {
+    let drafts = match number {
+        Some(number) => vec![find_draft(number)?],
+        None => list_drafts()?,
+    };
+
+    let (in_use, drafts): (Vec<Draft>, Vec<Draft>) =
+        drafts.into_iter().partition(|draft| draft.in_use());
+    let count = drafts.len();
+    for draft in drafts {
+        draft.discard()?;
+    }
+    Ok((count, in_use.len()))
+}
@@ -4,112 +4,266 @@
 //! It manages the git commit-style parsing (title on first line, body after blank line)
 //! and file I/O operations for daily log files.
 
-use chrono::{Duration, Local, NaiveDate};
+use crate::{
+    date::{self, records_utc_offset, time_into_day, utc_offset_at},
+    daylog::{DayLog, Entry, Span, UNTITLED_MARKER},
+    draft::{Draft, DraftKind},
+    lock::{lock_log_file, write_atomic},
+    trash::{TrashReason, move_to_trash},
+};
+use chrono::{NaiveDate, NaiveTime, Timelike};
+use regex::Regex;
 use std::{
     env,
-    fs::{self, File, OpenOptions},
-    io::{Read, Write},
+    fs,
+    io::{self, IsTerminal, Read, Write},
     path::{Path, PathBuf},
     process::Command,
+    sync::LazyLock,
 };
 
-/// Returns the file path for today's log entry.
+/// The editor used when none is configured.
+const DEFAULT_EDITOR: &str = "vim";
+
+/// The default prefix of help lines in the editor.
+pub const DEFAULT_COMMENT_CHAR: &str = "#";
+
+/// Marks the start of the help below an entry in the editor, like the
+/// scissors line of `git commit --cleanup=scissors`.
+const SCISSORS: &str = "------------------------ >8 ------------------------";
+
+/// Matches `#hashtags` at the start of the text or after whitespace.
+static TAG_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:^|\s)#([A-Za-z][\w-]*)").unwrap());
+
+/// Returns the file path for a specific date's log entry.
 ///
-/// Generates a path in the format `{log_dir}/YYYY-MM-DD.md` for the current date.
+/// Generates a path in the format `{log_dir}/YYYY-MM-DD.md` for the given date.
 ///
 /// # Arguments
 ///
 /// * `log_dir` - The directory where log files are stored
-///
-/// # Example
-///
-/// ```rust
-/// use dailylog::entry::get_log_file_path;
-/// 
-/// let path = get_log_file_path("/home/user/.dailylog");
-/// // Returns something like "/home/user/.dailylog/2024-01-15.md"
-/// ```
-pub fn get_log_file_path(log_dir: &str) -> PathBuf {
-    let date = Local::now().format("%Y-%m-%d").to_string();
-    Path::new(log_dir).join(format!("{date}.md"))
+/// * `date` - The specific date for the log entry
+pub fn get_log_file_path_for_date(log_dir: &str, date: NaiveDate) -> PathBuf {
+    let date_str = date.format("%Y-%m-%d").to_string();
+    Path::new(log_dir).join(format!("{date_str}.md"))
 }
 
-/// Returns the file path for yesterday's log entry.
+/// Lists the dates of all daily log files in the log directory.
 ///
-/// Generates a path in the format `{log_dir}/YYYY-MM-DD.md` for yesterday's date.
+/// Only files named `YYYY-MM-DD.md` directly inside `log_dir` are considered.
+/// The returned dates are sorted from oldest to newest.
 ///
 /// # Arguments
 ///
 /// * `log_dir` - The directory where log files are stored
-pub fn get_previous_day_log_path(log_dir: &str) -> PathBuf {
-    let yesterday = Local::now() - Duration::days(1);
-    let date = yesterday.format("%Y-%m-%d").to_string();
-    Path::new(log_dir).join(format!("{date}.md"))
+///
+/// # Errors
+///
+/// Returns an error if the log directory cannot be read.
+pub fn list_log_dates(log_dir: &str) -> anyhow::Result<Vec<NaiveDate>> {
+    let mut dates = Vec::new();
+
+    for dir_entry in fs::read_dir(log_dir)? {
+        let path = dir_entry?.path();
+        if !path.is_file() || path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+            continue;
+        }
+        let date = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| NaiveDate::parse_from_str(stem, "%Y-%m-%d").ok());
+        if let Some(date) = date {
+            dates.push(date);
+        }
+    }
+
+    dates.sort();
+    Ok(dates)
 }
 
-/// Returns the file path for a specific date's log entry.
+/// Extracts `#hashtags` from text.
 ///
-/// Generates a path in the format `{log_dir}/YYYY-MM-DD.md` for the given date.
+/// A tag is a `#` at the start of the text or after whitespace, followed by a
+/// letter and then letters, digits, `_` or `-`. Markdown headers (`## `) and
+/// issue references like `#123` are not tags. Tags are returned lowercased,
+/// without the `#`, de-duplicated and in order of first appearance.
 ///
 /// # Arguments
 ///
-/// * `log_dir` - The directory where log files are stored
-/// * `date` - The specific date for the log entry
-pub fn get_log_file_path_for_date(log_dir: &str, date: NaiveDate) -> PathBuf {
-    let date_str = date.format("%Y-%m-%d").to_string();
-    Path::new(log_dir).join(format!("{date_str}.md"))
+/// * `text` - The text to scan for tags
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::entry::extract_tags;
+///
+/// let tags = extract_tags("Paged for #oncall, see #ProjectX and #123");
+/// assert_eq!(tags, vec!["oncall", "projectx"]);
+/// ```
+pub fn extract_tags(text: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+
+    for captures in TAG_PATTERN.captures_iter(text) {
+        let tag = captures[1].trim_end_matches('-').to_lowercase();
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    tags
 }
 
 /// Opens the user's preferred editor to create a journal entry.
 ///
-/// Creates a temporary file and launches the editor specified by the `$EDITOR`
-/// environment variable (defaults to `vim` if not set). After the editor closes,
-/// reads and returns the content that was written.
+/// Like `git commit`, the editor buffer starts with the `template` (if any)
+/// followed by commented-out `help`. The buffer is the session's private
+/// draft file, which the caller deletes once the entry is saved. The editor
+/// is chosen by `editor_command`. After the editor closes, the help is
+/// removed and the remaining content is returned.
+/// If the user saves the template unchanged, an empty string is returned so
+/// that no entry is written.
+///
+/// # Arguments
+///
+/// * `draft` - The session's draft file, from `Draft::create`
+/// * `template` - An expanded entry template to start from
+/// * `help` - Commented-out context to show below the entry, from `entry_help`
+/// * `editor` - The `editor` config option, if set
 ///
 /// # Returns
 ///
-/// The content written in the editor as a string.
+/// The content written in the editor as a string, without the help.
 ///
 /// # Errors
 ///
 /// Returns an error if:
-/// - The temporary file cannot be created
-/// - The editor fails to launch
-/// - The temporary file cannot be read after editing
+/// - The draft file cannot be written to
+/// - The editor fails to launch or exits with a non-zero status
+/// - The draft file cannot be read after editing
 ///
 /// # Example
 ///
 /// ```rust
-/// use dailylog::entry::open_editor;
+/// use dailylog::date::today;
+/// use dailylog::draft::{Draft, DraftKind};
+/// use dailylog::entry::{entry_help, open_editor, DEFAULT_COMMENT_CHAR};
 ///
-/// let content = open_editor()?;
+/// let draft = Draft::create(DraftKind::Entry, today())?;
+/// let help = entry_help("/path/to/logs", today(), DEFAULT_COMMENT_CHAR)?;
+/// let content = open_editor(&draft, None, &help, None)?;
 /// println!("User wrote: {}", content);
 /// ```
-pub fn open_editor() -> anyhow::Result<String> {
-    let editor = env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
-    let mut temp_path = env::temp_dir();
-    temp_path.push("dailylog.md");
+pub fn open_editor(
+    draft: &Draft,
+    template: Option<&str>,
+    help: &str,
+    editor: Option<&str>,
+) -> anyhow::Result<String> {
+    let template = template.unwrap_or_default();
+    let buffer = format!("{}\n\n{}", template.trim_end(), help);
+
+    let edited = strip_help(&open_editor_with_content(draft, &buffer, editor)?);
+    if edited.trim() == template.trim() {
+        return Ok(String::new());
+    }
+    Ok(edited)
+}
 
-    File::create(&temp_path)?;
+/// Builds the commented-out help shown below a new entry in the editor.
+///
+/// Starts with a scissors line, below which everything is dropped by
+/// `strip_help`, then explains the entry format and lists the titles already
+/// logged on `date`, so there is no need to look them up before writing.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory containing log files
+/// * `date` - The day the entry is being written for
+/// * `comment_char` - The prefix of each help line
+///
+/// # Errors
+///
+/// Returns an error if the day's log cannot be read.
+pub fn entry_help(log_dir: &str, date: NaiveDate, comment_char: &str) -> anyhow::Result<String> {
+    let day = DayLog::load(log_dir, date)?;
+    let mut lines = vec![
+        SCISSORS.to_string(),
+        "Do not modify or remove the line above.".to_string(),
+        "Everything below it will be ignored.".to_string(),
+        String::new(),
+        format!("Writing an entry for {}.", date.format("%A %Y-%m-%d")),
+        "The first line is the title; the body starts after the first blank line.".to_string(),
+        "An empty entry aborts.".to_string(),
+        String::new(),
+    ];
 
-    Command::new(editor)
-        .arg(&temp_path)
-        .status()
-        .expect("Failed to launch editor");
+    let headers: Vec<String> = day.entries.iter().filter_map(|entry| entry.header()).collect();
+    if headers.is_empty() {
+        lines.push("Nothing logged for this day yet.".to_string());
+    } else {
+        lines.push(format!("Already logged ({}):", headers.len()));
+        for header in headers {
+            lines.push(format!("  {}", header.trim_start_matches("## ")));
+        }
+    }
 
-    let mut contents = String::new();
-    File::open(&temp_path)?.read_to_string(&mut contents)?;
-    Ok(contents)
+    Ok(lines
+        .iter()
+        .map(|line| format!("{} {}", comment_char, line).trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n")
+}
+
+/// Returns the command line of the user's preferred editor.
+///
+/// Like git, uses the first of `$VISUAL`, `$EDITOR`, the `editor` config
+/// option and `vim` that is set and non-empty. The value is split into words
+/// the way a shell would, so commands with arguments such as `code --wait`
+/// or `emacsclient -t` work, and quoted paths may contain spaces.
+///
+/// # Arguments
+///
+/// * `configured` - The `editor` config option, if set
+///
+/// # Errors
+///
+/// Returns an error if the command has unbalanced quotes.
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::entry::editor_command;
+///
+/// let command = editor_command(Some("code --wait"))?;
+/// ```
+pub fn editor_command(configured: Option<&str>) -> anyhow::Result<Vec<String>> {
+    let from_env = |name| env::var(name).ok().filter(|value: &String| !value.trim().is_empty());
+    let editor = from_env("VISUAL")
+        .or_else(|| from_env("EDITOR"))
+        .or_else(|| configured.filter(|value| !value.trim().is_empty()).map(str::to_string))
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string());
+
+    shell_words::split(&editor)
+        .map_err(|e| anyhow::anyhow!("Invalid editor command '{}': {}", editor, e))
 }
 
 /// Opens the user's preferred editor with existing content pre-loaded.
 ///
-/// Creates a temporary file with the provided content and launches the editor.
-/// After the editor closes, reads and returns the modified content.
+/// Writes the content to the session's private draft file and launches the
+/// editor (see `editor_command`) on it. After the editor closes, reads and
+/// returns the modified content. The draft is left in place; the caller
+/// deletes it once the content has been saved.
+///
+/// As with git, an editor that exits with a non-zero status (such as vim's
+/// `:cq`) aborts the edit. The draft is kept so the text can be recovered.
 ///
 /// # Arguments
 ///
+/// * `draft` - The session's draft file, from `Draft::create`
 /// * `existing_content` - Content to pre-load in the editor
+/// * `editor` - The `editor` config option, if set
 ///
 /// # Returns
 ///
@@ -118,26 +272,116 @@ pub fn open_editor() -> anyhow::Result<String> {
 /// # Errors
 ///
 /// Returns an error if:
-/// - The temporary file cannot be created or written to
-/// - The editor fails to launch
-/// - The temporary file cannot be read after editing
-pub fn open_editor_with_content(existing_content: &str) -> anyhow::Result<String> {
-    let editor = env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
-    let mut temp_path = env::temp_dir();
-    temp_path.push("dailylog.md");
-
-    // Write existing content to temp file
-    let mut file = File::create(&temp_path)?;
-    file.write_all(existing_content.as_bytes())?;
-    drop(file); // Ensure file is closed before opening in editor
-
-    Command::new(editor)
-        .arg(&temp_path)
-        .status()
-        .expect("Failed to launch editor");
+/// - The draft file cannot be written to
+/// - The editor command is invalid or fails to launch
+/// - The editor exits with a non-zero status
+/// - The draft file cannot be read after editing
+pub fn open_editor_with_content(
+    draft: &Draft,
+    existing_content: &str,
+    editor: Option<&str>,
+) -> anyhow::Result<String> {
+    draft.write(existing_content)?;
+    let launched = editor_command(editor).and_then(|command| {
+        let (program, args) = command
+            .split_first()
+            .ok_or_else(|| anyhow::anyhow!("The editor command is empty"))?;
+        Command::new(program)
+            .args(args)
+            .arg(&draft.path)
+            .status()
+            .map(|status| (command.join(" "), status))
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to launch editor '{}': {}. Set $VISUAL, $EDITOR or `editor` in ~/.dailylog.toml.",
+                    command.join(" "),
+                    e
+                )
+            })
+    });
+
+    let (command, status) = match launched {
+        Ok(launched) => launched,
+        Err(e) => {
+            // The editor never ran, so there is nothing to recover
+            draft.discard().ok();
+            return Err(e);
+        }
+    };
+    if !status.success() {
+        let reason = match status.code() {
+            Some(code) => format!("exited with status {}", code),
+            None => "was terminated".to_string(),
+        };
+        return Err(anyhow::anyhow!(
+            "Editor '{}' {}; nothing was saved. Your text was kept as a draft, see `dailylog drafts`.",
+            command,
+            reason
+        ));
+    }
+
+    draft.read()
+}
+
+/// Removes the help that `entry_help` adds below an entry in the editor.
+///
+/// Everything from the scissors line down is dropped, whatever prefix it has,
+/// so the entry itself is kept as written, markdown `# headings` included.
+/// Leading blank lines are trimmed so the first line written becomes the
+/// entry title.
+///
+/// # Arguments
+///
+/// * `content` - The content written in the editor
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::entry::strip_help;
+///
+/// let content = "Shipped it\n\n# Notes\n\n# ------------------------ >8 ------------------------\n# Help";
+/// assert_eq!(strip_help(content), "Shipped it\n\n# Notes");
+/// ```
+pub fn strip_help(content: &str) -> String {
+    content
+        .lines()
+        .take_while(|line| !line.trim_end().ends_with(SCISSORS))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_start_matches(['\n', '\r'])
+        .trim_end()
+        .to_string()
+}
+
+/// Builds entry content from command-line messages.
+///
+/// Mirrors `git commit -m`: each message becomes its own paragraph, so the
+/// first message is the title and any further messages form the body.
+///
+/// # Arguments
+///
+/// * `messages` - The messages given via repeated `-m` flags
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::entry::entry_from_messages;
+///
+/// let content = entry_from_messages(&["Deployed v2".to_string(), "No downtime.".to_string()]);
+/// assert_eq!(content, "Deployed v2\n\nNo downtime.");
+/// ```
+pub fn entry_from_messages(messages: &[String]) -> String {
+    messages.join("\n\n")
+}
 
+/// Reads entry content from standard input until EOF.
+///
+/// # Errors
+///
+/// Returns an error if stdin cannot be read.
+pub fn read_entry_from_stdin() -> anyhow::Result<String> {
     let mut contents = String::new();
-    File::open(&temp_path)?.read_to_string(&mut contents)?;
+    io::stdin().read_to_string(&mut contents)?;
     Ok(contents)
 }
 
@@ -210,6 +454,9 @@ pub fn parse_entry(content: &str) -> (Option<String>, String) {
 /// - A level 2 header with timestamp and title (if title exists)
 /// - The body content below (if body exists)
 ///
+/// The timestamp is in the configured timezone, followed by its UTC offset
+/// when `record_utc_offset` is set.
+///
 /// # Arguments
 ///
 /// * `title` - Optional title for the entry
@@ -228,29 +475,20 @@ pub fn parse_entry(content: &str) -> (Option<String>, String) {
 /// // Returns something like: "## 14:30 - Meeting notes\n\nDiscussed project timeline\n"
 /// ```
 pub fn format_entry(title: Option<&str>, body: &str) -> String {
-    match title {
-        Some(title) if !title.is_empty() => {
-            let timestamp = Local::now().format("%H:%M").to_string();
-            if body.is_empty() {
-                format!("## {} - {}\n", timestamp, title)
-            } else {
-                format!("## {} - {}\n\n{}\n", timestamp, title, body)
-            }
-        }
-        _ => {
-            if body.is_empty() {
-                String::new()
-            } else {
-                format!("{}\n", body)
-            }
-        }
-    }
+    let now = date::now();
+    let time = NaiveTime::from_hms_opt(now.hour(), now.minute(), 0);
+    let offset = records_utc_offset().then(|| *now.offset());
+    let title = title.filter(|title| !title.is_empty());
+    Entry::new(time, title.map(str::to_string), body.to_string())
+        .with_offset(offset)
+        .to_markdown()
 }
 
 /// Appends a new entry to a log file.
 ///
 /// Parses the content using git commit style, formats it with a timestamp,
 /// and appends it to the specified log file. Creates the file if it doesn't exist.
+/// The file is locked while it is rewritten, so concurrent appends are never lost.
 ///
 /// # Arguments
 ///
@@ -259,7 +497,7 @@ pub fn format_entry(title: Option<&str>, body: &str) -> String {
 ///
 /// # Errors
 ///
-/// Returns an error if the file cannot be opened or written to.
+/// Returns an error if the file cannot be locked, read or written to.
 ///
 /// # Example
 ///
@@ -275,57 +513,861 @@ pub fn append_to_log(path: &Path, content: &str) -> anyhow::Result<()> {
     let formatted_entry = format_entry(title.as_deref(), &body);
 
     if !formatted_entry.trim().is_empty() {
-        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
-        writeln!(file, "{}", formatted_entry)?;
+        let _lock = lock_log_file(path)?;
+        let log = read_log_file(path)?;
+        let mut entry = String::new();
+        if title.is_none() && !log.trim().is_empty() {
+            // Without the marker, the text would read as part of the entry before it
+            entry.push_str(UNTITLED_MARKER);
+            entry.push('\n');
+        }
+        entry.push_str(formatted_entry.trim_matches(['\r', '\n']));
+        entry.push('\n');
+        write_atomic(path, &append_entry_text(&log, &entry))?;
     }
 
     Ok(())
 }
 
-/// Edits today's log file in-place using the user's preferred editor.
+/// Replaces a day's log, moving the version it replaces to the trash.
+///
+/// An empty (or blank) `content` deletes the log file. Callers must hold the
+/// file's lock (see `lock_log_file`) and pass its current content as `previous`.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory containing log files
+/// * `date` - The day whose log to replace
+/// * `previous` - The log's current content, empty if there is no log
+/// * `content` - The new content
+///
+/// # Returns
+///
+/// The path of the trashed copy of `previous`, unless it was blank or unchanged.
+///
+/// # Errors
+///
+/// Returns an error if the trash or log file cannot be written.
+pub fn write_day_log(
+    log_dir: &str,
+    date: NaiveDate,
+    previous: &str,
+    content: &str,
+) -> anyhow::Result<Option<PathBuf>> {
+    if previous == content {
+        return Ok(None);
+    }
+
+    let path = get_log_file_path_for_date(log_dir, date);
+    let deleting = content.trim().is_empty();
+    let trashed = if previous.trim().is_empty() {
+        None
+    } else {
+        let reason = if deleting {
+            TrashReason::Deleted
+        } else {
+            TrashReason::Replaced
+        };
+        Some(move_to_trash(log_dir, date, previous, reason)?)
+    };
+
+    if !deleting {
+        write_atomic(&path, content)?;
+    } else if path.exists() {
+        fs::remove_file(&path)?;
+    }
+    Ok(trashed)
+}
+
+/// Returns the content of a log file, or an empty string if it does not exist.
+///
+/// # Arguments
+///
+/// * `path` - Path to the log file
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read.
+pub fn read_log_file(path: &Path) -> anyhow::Result<String> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(anyhow::anyhow!("Failed to read {:?}: {}", path, e)),
+    }
+}
+
+/// Asks a yes/no question on the terminal, defaulting to no.
+///
+/// Returns `false` without asking when stdin is not a terminal.
+pub fn confirm(question: &str) -> anyhow::Result<bool> {
+    if !io::stdin().is_terminal() {
+        return Ok(false);
+    }
+
+    eprint!("{} [y/N] ", question);
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Asks what to do with an edit when the log changed on disk meanwhile.
+///
+/// Merging keeps the edited text and adds the entries from the file that
+/// were not in the version the edit started from. Overwriting saves the
+/// edited text as is. Without a terminal to ask on, the edit is aborted.
 ///
-/// Reads the existing content of today's log file (if it exists), opens it in the editor,
+/// Returns the content to save, or `None` to abort.
+fn resolve_edit_conflict(
+    date: NaiveDate,
+    original: &str,
+    on_disk: &str,
+    edited: &str,
+) -> anyhow::Result<Option<String>> {
+    // Match entries one to one, so a repeated entry still counts as new
+    let mut original_entries = DayLog::parse(date, original).entries;
+    let added = DayLog {
+        date,
+        entries: DayLog::parse(date, on_disk)
+            .entries
+            .into_iter()
+            .filter(|entry| match original_entries.iter().position(|e| e == entry) {
+                Some(i) => {
+                    original_entries.remove(i);
+                    false
+                }
+                None => true,
+            })
+            .collect(),
+    };
+
+    let merged = if added.is_empty() {
+        edited.to_string()
+    } else if edited.trim().is_empty() {
+        added.to_markdown()
+    } else {
+        // The first added entry follows the edited text now, so an untitled one needs its marker
+        let marker = match added.entries[0].title {
+            Some(_) => String::new(),
+            None => format!("{}\n", UNTITLED_MARKER),
+        };
+        let added = format!("{}{}\n", marker, added.to_markdown().trim_end());
+        append_entry_text(edited, &added)
+    };
+
+    eprintln!(
+        "The log for {} changed on disk while the editor was open ({} new {}).",
+        date.format("%Y-%m-%d"),
+        added.entries.len(),
+        if added.entries.len() == 1 { "entry" } else { "entries" }
+    );
+    if !io::stdin().is_terminal() {
+        return Ok(None);
+    }
+
+    loop {
+        eprint!("[m]erge the new entries into your edit, [o]verwrite them, or [a]bort? ");
+        io::stderr().flush()?;
+        let mut answer = String::new();
+        if io::stdin().read_line(&mut answer)? == 0 {
+            return Ok(None);
+        }
+        match answer.trim().to_lowercase().as_str() {
+            "m" | "merge" => return Ok(Some(merged)),
+            "o" | "overwrite" => return Ok(Some(edited.to_string())),
+            "a" | "abort" | "" => return Ok(None),
+            _ => continue,
+        }
+    }
+}
+
+/// What `edit_day_log` did with a day's log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditOutcome {
+    /// The log was left as it was
+    Unchanged,
+    /// The edited log was saved
+    Updated,
+    /// The log was cleared, so its file was deleted and its content trashed
+    Deleted,
+}
+
+impl EditOutcome {
+    /// Describes the outcome for the user.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The day whose log was edited
+    /// * `path` - Path to the day's log file
+    pub fn message(self, date: NaiveDate, path: &Path) -> String {
+        match self {
+            EditOutcome::Unchanged => format!("Log for {} left unchanged.", date.format("%Y-%m-%d")),
+            EditOutcome::Updated => format!("Log updated at {:?}", path),
+            EditOutcome::Deleted => {
+                format!("Log for {} deleted (moved to trash)", date.format("%Y-%m-%d"))
+            }
+        }
+    }
+}
+
+/// Edits a day's log file in-place using the user's preferred editor.
+///
+/// Reads the existing content of the log file (if it exists), opens it in the editor,
 /// and saves the modified content back to the file. If the log file doesn't exist,
-/// starts with an empty file.
+/// starts with an empty file. If saving fails, the edited content is kept as a draft.
+///
+/// The file is not locked while the editor is open. If it changed on disk in the
+/// meantime (say, an entry was added from another terminal), the user is asked
+/// whether to merge the new entries into the edit, overwrite them, or abort.
+///
+/// The version of the log being replaced is moved to the trash. Clearing the
+/// whole log deletes the file, after asking for confirmation unless `force`
+/// is set.
 ///
 /// # Arguments
 ///
-/// * `path` - Path to today's log file
+/// * `log_dir` - The directory containing log files
+/// * `date` - The day whose log to edit
+/// * `editor` - The `editor` config option, if set
+/// * `force` - Delete a cleared log without asking
+///
+/// # Returns
+///
+/// Whether the log was left unchanged, updated or deleted.
 ///
 /// # Errors
 ///
 /// Returns an error if:
-/// - The file cannot be read or written
-/// - The editor fails to launch
-/// - The temporary file operations fail
+/// - The file cannot be read, locked or written
+/// - The editor fails to launch or exits with a non-zero status
+/// - The file changed on disk and the edit was aborted
+/// - The log was cleared and deleting it was not confirmed
+/// - The draft file operations fail
 ///
 /// # Example
 ///
 /// ```rust
-/// use std::path::Path;
-/// use dailylog::entry::edit_today_log;
+/// use chrono::NaiveDate;
+/// use dailylog::entry::edit_day_log;
 ///
-/// edit_today_log(Path::new("2024-01-15.md"))?;
+/// edit_day_log("/path/to/logs", NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(), None, false)?;
 /// ```
-pub fn edit_today_log(path: &Path) -> anyhow::Result<()> {
+pub fn edit_day_log(
+    log_dir: &str,
+    date: NaiveDate,
+    editor: Option<&str>,
+    force: bool,
+) -> anyhow::Result<EditOutcome> {
+    let path = &get_log_file_path_for_date(log_dir, date);
+
     // Read existing content if the file exists
-    let existing_content = if path.exists() {
-        fs::read_to_string(path)?
+    let existing_content = read_log_file(path)?;
+
+    // Open editor with existing content
+    let draft = Draft::create(DraftKind::Edit, date)?;
+    let new_content = open_editor_with_content(&draft, &existing_content, editor)?;
+    if new_content == existing_content {
+        draft.discard()?;
+        return Ok(EditOutcome::Unchanged);
+    }
+
+    // Clearing the buffer by accident should not cost a day of notes
+    if new_content.trim().is_empty()
+        && !existing_content.trim().is_empty()
+        && !force
+        && !confirm(&format!(
+            "Delete the log for {}? It will be moved to the trash.",
+            date.format("%Y-%m-%d")
+        ))?
+    {
+        draft.discard()?;
+        return Err(anyhow::anyhow!(
+            "The log for {} was not deleted. Use --force to delete it without asking.",
+            date.format("%Y-%m-%d")
+        ));
+    }
+
+    let mut outcome = EditOutcome::Updated;
+    draft.save_with(|| {
+        let _lock = lock_log_file(path)?;
+        let on_disk = read_log_file(path)?;
+        let new_content = if on_disk == existing_content {
+            new_content
+        } else {
+            resolve_edit_conflict(date, &existing_content, &on_disk, &new_content)?.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "The log for {} changed on disk while the editor was open; nothing was saved.",
+                    date.format("%Y-%m-%d")
+                )
+            })?
+        };
+
+        // If the user cleared all content, the file is removed
+        if new_content.trim().is_empty() && path.exists() {
+            outcome = EditOutcome::Deleted;
+        }
+        let trashed = write_day_log(log_dir, date, &on_disk, &new_content)?;
+        if let Some(trashed) = trashed
+            && outcome == EditOutcome::Updated
+        {
+            println!("The previous version was moved to the trash: {:?}", trashed);
+        }
+        Ok(())
+    })?;
+    Ok(outcome)
+}
+
+/// Returns an entry as it is shown in the editor: the title, a blank line
+/// and the body, like a git commit message.
+fn entry_text(entry: &Entry) -> String {
+    match &entry.title {
+        Some(title) if entry.body.is_empty() => format!("{}\n", title),
+        Some(title) => format!("{}\n\n{}\n", title, entry.body),
+        None => format!("{}\n", entry.body),
+    }
+}
+
+/// Edits a single entry of a day's log in the user's preferred editor.
+///
+/// The entry is opened on its own, with its title on the first line and its
+/// body after a blank line. The day's log is then written back with the
+/// entry replaced (see `replace_entry`). The version of the log being
+/// replaced is moved to the trash.
+///
+/// If saving fails, or the entry changed on disk while the editor was open,
+/// the edited text is kept as an entry-edit draft, which replaces the same
+/// entry when restored.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory containing log files
+/// * `date` - The day the entry belongs to
+/// * `selector` - The entry's number (starting at 1) or its `HH:MM` time
+/// * `editor` - The `editor` config option, if set
+///
+/// # Returns
+///
+/// `true` if the entry was changed, `false` if it was left as it was or
+/// emptied (use `remove_entry` to delete an entry).
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - No entry matches `selector`
+/// - The log file cannot be read, locked or written
+/// - The editor fails to launch or exits with a non-zero status
+/// - The edited entry has a blank title, or the entry changed on disk while
+///   the editor was open
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use dailylog::entry::edit_entry;
+///
+/// let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+/// edit_entry("/path/to/logs", date, "14:30", None)?;
+/// ```
+pub fn edit_entry(
+    log_dir: &str,
+    date: NaiveDate,
+    selector: &str,
+    editor: Option<&str>,
+) -> anyhow::Result<bool> {
+    let path = &get_log_file_path_for_date(log_dir, date);
+    let day = DayLog::parse(date, &read_log_file(path)?);
+    let index = day.find_entry(selector)?;
+    let original = day.entries[index].clone();
+    let original_text = entry_text(&original);
+
+    let draft = Draft::create(DraftKind::EntryEdit, date)?;
+    draft.write_original(&original.to_markdown())?;
+    let content = open_editor_with_content(&draft, &original_text, editor)?;
+    if content.trim().is_empty() || content.trim() == original_text.trim() {
+        draft.discard()?;
+        return Ok(false);
+    }
+
+    draft.save_with(|| replace_entry(log_dir, date, &original, &content))?;
+    Ok(true)
+}
+
+/// Replaces an entry of a day's log with edited text.
+///
+/// The first line of `content` is the entry's new title and the rest its
+/// body, as `edit_entry` lays it out. The entry keeps its `## HH:MM - title`
+/// header, its time and its UTC offset. An untitled entry stays untitled,
+/// and all of `content` is treated as body.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory containing log files
+/// * `date` - The day the entry belongs to
+/// * `original` - The entry as it was before editing
+/// * `content` - The edited text
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - `original` has a title and the first line of `content` is blank
+/// - The day's log no longer contains `original`
+/// - The log file cannot be read, locked or written
+pub fn replace_entry(
+    log_dir: &str,
+    date: NaiveDate,
+    original: &Entry,
+    content: &str,
+) -> anyhow::Result<()> {
+    let edited = match original.title {
+        Some(_) => match parse_entry(content) {
+            (Some(title), body) if !title.trim().is_empty() => {
+                Entry::new(original.time, Some(title), body.trim().to_string())
+                    .with_offset(original.offset)
+            }
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "The entry's title (its first line) cannot be blank. Use `dailylog entry rm` to remove an entry."
+                ));
+            }
+        },
+        None => Entry::new(None, None, content.trim().to_string()),
+    };
+
+    update_day_log(log_dir, date, |content, day, spans| {
+        let index = day
+            .entries
+            .iter()
+            .position(|entry| entry == original)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "The entry on {} changed on disk since it was opened; nothing was saved.",
+                    date.format("%Y-%m-%d")
+                )
+            })?;
+        Ok((replace_entry_text(content, &spans[index], &edited), ()))
+    })
+}
+
+/// Applies `change` to a day's log while holding its lock, then writes the
+/// log back, moving the previous version to the trash.
+///
+/// `change` gets the log's content, its entries and their spans, and returns
+/// the new content. Changes splice single entries in and out of the content,
+/// so the rest of the file keeps its exact text and line endings.
+fn update_day_log<T>(
+    log_dir: &str,
+    date: NaiveDate,
+    change: impl FnOnce(&str, &DayLog, &[Span]) -> anyhow::Result<(String, T)>,
+) -> anyhow::Result<T> {
+    let path = get_log_file_path_for_date(log_dir, date);
+    let _lock = lock_log_file(&path)?;
+    let on_disk = read_log_file(&path)?;
+    let (day, spans) = DayLog::parse_with_spans(date, &on_disk);
+    let (content, result) = change(&on_disk, &day, &spans)?;
+    write_day_log(log_dir, date, &on_disk, &content)?;
+    Ok(result)
+}
+
+/// Returns the line ending `content` uses: `\r\n` if it has any, else `\n`.
+fn line_ending(content: &str) -> &'static str {
+    if content.contains("\r\n") {
+        "\r\n"
     } else {
-        String::new()
+        "\n"
+    }
+}
+
+/// Returns `text` with its line endings changed to `\r\n` if `content` uses them.
+fn with_line_endings(content: &str, text: &str) -> String {
+    match line_ending(content) {
+        "\n" => text.to_string(),
+        ending => text.replace('\n', ending),
+    }
+}
+
+/// Returns `content` with the entry at `span` and the blank lines after it
+/// removed.
+fn cut_entry(content: &str, span: &Span) -> String {
+    format!("{}{}", &content[..span.start], &content[span.end..])
+}
+
+/// Returns `content` with the text of the entry at `span` replaced by
+/// `entry`, keeping the blank lines after it and, for an untitled entry, the
+/// marker line before it.
+fn replace_entry_text(content: &str, span: &Span, entry: &Entry) -> String {
+    let mut start = span.start;
+    if entry.title.is_none() && content[start..].trim_start().starts_with(UNTITLED_MARKER) {
+        start += content[start..span.text_end].find('\n').map_or(0, |i| i + 1);
+    }
+    format!(
+        "{}{}{}",
+        &content[..start],
+        with_line_endings(content, &entry.to_markdown()),
+        &content[span.text_end..]
+    )
+}
+
+/// Returns `content` with the header line of the entry at `span` replaced by
+/// `header`, keeping the line ending.
+fn replace_header(content: &str, span: &Span, header: &str) -> String {
+    let line = &content[span.start..span.text_end];
+    let line = line.split_inclusive('\n').next().unwrap_or(line);
+    let line = line.trim_end_matches(['\r', '\n']);
+    format!(
+        "{}{}{}",
+        &content[..span.start],
+        header,
+        &content[span.start + line.len()..]
+    )
+}
+
+/// Returns `content` with `text` (one or more whole lines) added at the end,
+/// after a blank line, followed by the blank line that ends every entry.
+fn append_entry_text(content: &str, text: &str) -> String {
+    let ending = line_ending(content);
+    let mut out = content.to_string();
+    if !out.trim().is_empty() {
+        if !out.ends_with('\n') {
+            out.push_str(ending);
+        }
+        if !out.ends_with(&ending.repeat(2)) {
+            out.push_str(ending);
+        }
+    }
+    out.push_str(&with_line_endings(content, text));
+    out.push_str(ending);
+    out
+}
+
+/// Returns `content` with `entry` added as entry number `position` (starting
+/// at 0) of `day`, whose entries are at `spans`.
+fn insert_entry(
+    content: &str,
+    day: &DayLog,
+    spans: &[Span],
+    position: usize,
+    entry: &Entry,
+) -> String {
+    let mut text = String::new();
+    if entry.title.is_none() && position > 0 {
+        text.push_str(UNTITLED_MARKER);
+        text.push('\n');
+    }
+    text.push_str(&entry.to_markdown());
+
+    let Some(next) = spans.get(position) else {
+        return append_entry_text(content, &text);
     };
+    text.push('\n');
+    // The untitled entry that came first is no longer first, so it needs a marker
+    if day.entries[position].title.is_none()
+        && !content[next.start..].trim_start().starts_with(UNTITLED_MARKER)
+    {
+        text.push_str(UNTITLED_MARKER);
+        text.push('\n');
+    }
+    format!(
+        "{}{}{}",
+        &content[..next.start],
+        with_line_endings(content, &text),
+        &content[next.start..]
+    )
+}
 
-    // Open editor with existing content
-    let new_content = open_editor_with_content(&existing_content)?;
+/// Deletes a single entry from a day's log.
+///
+/// The log as it was before is moved to the trash. Removing the last entry
+/// deletes the log file.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory containing log files
+/// * `date` - The day the entry belongs to
+/// * `selector` - The entry's number (starting at 1) or its `HH:MM` time
+///
+/// # Returns
+///
+/// The removed entry.
+///
+/// # Errors
+///
+/// Returns an error if no entry matches `selector`, or the log cannot be
+/// read, locked or written.
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use dailylog::entry::remove_entry;
+///
+/// let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+/// let removed = remove_entry("/path/to/logs", date, "2")?;
+/// ```
+pub fn remove_entry(log_dir: &str, date: NaiveDate, selector: &str) -> anyhow::Result<Entry> {
+    update_day_log(log_dir, date, |content, day, spans| {
+        let index = day.find_entry(selector)?;
+        Ok((cut_entry(content, &spans[index]), day.entries[index].clone()))
+    })
+}
 
-    // Only write if content has changed or if it's not empty
-    if new_content != existing_content && !new_content.trim().is_empty() {
-        fs::write(path, new_content)?;
-    } else if new_content.trim().is_empty() && path.exists() {
-        // If user cleared all content, remove the file
-        fs::remove_file(path)?;
-        println!("Log file removed (content was empty)");
+/// Moves a single entry to another day's log.
+///
+/// The entry keeps its time and is written exactly as `format_entry` would,
+/// placed before the first entry on `to` with a later time (counting from
+/// `day_starts_at`, so entries after midnight come last). Both logs are
+/// locked, and their previous versions are moved to the trash.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory containing log files
+/// * `from` - The day the entry is logged on now
+/// * `selector` - The entry's number (starting at 1) or its `HH:MM` time
+/// * `to` - The day to move the entry to
+///
+/// # Returns
+///
+/// The moved entry.
+///
+/// # Errors
+///
+/// Returns an error if `from` and `to` are the same day, no entry matches
+/// `selector`, or either log cannot be read, locked or written.
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use dailylog::entry::move_entry;
+///
+/// let monday = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+/// let friday = NaiveDate::from_ymd_opt(2024, 1, 12).unwrap();
+/// move_entry("/path/to/logs", monday, "09:30", friday)?;
+/// ```
+pub fn move_entry(
+    log_dir: &str,
+    from: NaiveDate,
+    selector: &str,
+    to: NaiveDate,
+) -> anyhow::Result<Entry> {
+    if from == to {
+        return Err(anyhow::anyhow!(
+            "The entry is already logged on {}",
+            to.format("%Y-%m-%d")
+        ));
     }
 
-    Ok(())
-}
\ No newline at end of file
+    let from_path = get_log_file_path_for_date(log_dir, from);
+    let to_path = get_log_file_path_for_date(log_dir, to);
+
+    // Both logs are in the log directory, so one lock covers them
+    let _lock = lock_log_file(&from_path)?;
+
+    let from_content = read_log_file(&from_path)?;
+    let to_content = read_log_file(&to_path)?;
+    let (from_day, from_spans) = DayLog::parse_with_spans(from, &from_content);
+    let (to_day, to_spans) = DayLog::parse_with_spans(to, &to_content);
+
+    let index = from_day.find_entry(selector)?;
+    let entry = from_day.entries[index].clone();
+    let position = match entry.time {
+        Some(time) if entry.title.is_some() => to_day
+            .entries
+            .iter()
+            .position(|other| {
+                other.title.is_some()
+                    && other.time.is_some_and(|other| time_into_day(other) > time_into_day(time))
+            })
+            .unwrap_or(to_day.entries.len()),
+        _ => to_day.entries.len(),
+    };
+    let moved_to = insert_entry(&to_content, &to_day, &to_spans, position, &entry);
+    let moved_from = cut_entry(&from_content, &from_spans[index]);
+
+    // Write the destination first, so a failure never loses the entry
+    write_day_log(log_dir, to, &to_content, &moved_to)?;
+    write_day_log(log_dir, from, &from_content, &moved_from)?;
+    Ok(entry)
+}
+
+/// Changes the time in a single entry's `## HH:MM - title` header.
+///
+/// The entry stays where it is in the day's log. If its header records a
+/// UTC offset, the offset is worked out again for the new time in the
+/// configured timezone, so it stays right across a daylight saving change;
+/// it is dropped if the new time does not exist that day. The log as it was
+/// before is moved to the trash.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory containing log files
+/// * `date` - The day the entry belongs to
+/// * `selector` - The entry's number (starting at 1) or its `HH:MM` time
+/// * `time` - The entry's new time
+///
+/// # Returns
+///
+/// The entry with its new time.
+///
+/// # Errors
+///
+/// Returns an error if no entry matches `selector`, the entry has no title
+/// (and so no header to hold a time), or the log cannot be read, locked or
+/// written.
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::{NaiveDate, NaiveTime};
+/// use dailylog::entry::retime_entry;
+///
+/// let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+/// retime_entry("/path/to/logs", date, "1", NaiveTime::from_hms_opt(9, 30, 0).unwrap())?;
+/// ```
+pub fn retime_entry(
+    log_dir: &str,
+    date: NaiveDate,
+    selector: &str,
+    time: NaiveTime,
+) -> anyhow::Result<Entry> {
+    update_day_log(log_dir, date, |content, day, spans| {
+        let index = day.find_entry(selector)?;
+        let mut entry = day.entries[index].clone();
+        if entry.title.is_none() {
+            return Err(anyhow::anyhow!(
+                "Entry {} on {} has no title, so it has no time to change",
+                index + 1,
+                date.format("%Y-%m-%d")
+            ));
+        }
+        entry.time = Some(time);
+        if entry.offset.is_some() {
+            entry.offset = utc_offset_at(date, time);
+        }
+        let header = entry.header().unwrap_or_default();
+        Ok((replace_header(content, &spans[index], &header), entry))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> (DayLog, Vec<Span>) {
+        DayLog::parse_with_spans(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(), content)
+    }
+
+    #[test]
+    fn cutting_an_entry_leaves_the_rest_byte_for_byte() {
+        let content = "## 09:00 - Old style  \r\n\r\nline a\r\n\r\n\r\nline b\r\n\r\n## 10:00 - Keep  \r\n\r\nx\r\n";
+        let (_, spans) = parse(content);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(cut_entry(content, &spans[0]), "## 10:00 - Keep  \r\n\r\nx\r\n");
+        assert_eq!(
+            cut_entry(content, &spans[1]),
+            "## 09:00 - Old style  \r\n\r\nline a\r\n\r\n\r\nline b\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn replacing_a_header_keeps_its_line_ending_and_body() {
+        let content = "## 09:00 - Standup  \r\n\r\nbody\r\n";
+        let (day, spans) = parse(content);
+        let mut entry = day.entries[0].clone();
+        entry.time = NaiveTime::from_hms_opt(8, 45, 0);
+        let header = entry.header().unwrap();
+        assert_eq!(
+            replace_header(content, &spans[0], &header),
+            "## 08:45 - Standup\r\n\r\nbody\r\n"
+        );
+    }
+
+    #[test]
+    fn replacing_entry_text_keeps_the_separator_and_marker() {
+        let content = "## 09:00 - One\n\nbody\n\n\n<!-- untitled -->\nnote\n\n";
+        let (day, spans) = parse(content);
+
+        let edited = Entry::new(day.entries[0].time, Some("One".to_string()), "new".to_string());
+        assert_eq!(
+            replace_entry_text(content, &spans[0], &edited),
+            "## 09:00 - One\n\nnew\n\n\n<!-- untitled -->\nnote\n\n"
+        );
+
+        let edited = Entry::new(None, None, "new note".to_string());
+        assert_eq!(
+            replace_entry_text(content, &spans[1], &edited),
+            "## 09:00 - One\n\nbody\n\n\n<!-- untitled -->\nnew note\n\n"
+        );
+    }
+
+    #[test]
+    fn inserting_before_an_entry_matches_line_endings() {
+        let content = "## 09:00 - One\r\n\r\n## 11:00 - Three\r\n\r\n";
+        let (day, spans) = parse(content);
+        let entry = Entry::new(NaiveTime::from_hms_opt(10, 0, 0), Some("Two".to_string()), String::new());
+        assert_eq!(
+            insert_entry(content, &day, &spans, 1, &entry),
+            "## 09:00 - One\r\n\r\n## 10:00 - Two\r\n\r\n## 11:00 - Three\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn inserting_before_a_leading_untitled_entry_marks_it() {
+        let content = "note\n\n";
+        let (day, spans) = parse(content);
+        let entry = Entry::new(NaiveTime::from_hms_opt(9, 0, 0), Some("One".to_string()), String::new());
+        let inserted = insert_entry(content, &day, &spans, 0, &entry);
+        assert_eq!(inserted, "## 09:00 - One\n\n<!-- untitled -->\nnote\n\n");
+        assert_eq!(parse(&inserted).0.entries.len(), 2);
+    }
+
+    #[test]
+    fn appending_an_untitled_entry_marks_it() {
+        let content = "## 09:00 - One\n\nbody";
+        let (day, spans) = parse(content);
+        let entry = Entry::new(None, None, "note".to_string());
+        let appended = insert_entry(content, &day, &spans, 1, &entry);
+        assert_eq!(appended, "## 09:00 - One\n\nbody\n\n<!-- untitled -->\nnote\n\n");
+        assert_eq!(parse(&appended).0.entries[1].body, "note");
+    }
+
+    #[test]
+    fn appending_to_an_empty_log_adds_no_separator() {
+        assert_eq!(append_entry_text("", "## 09:00 - One\n"), "## 09:00 - One\n\n");
+        assert_eq!(append_entry_text("\n", "## 09:00 - One\n"), "\n## 09:00 - One\n\n");
+    }
+
+    #[test]
+    fn tags_start_at_whitespace_and_a_letter() {
+        assert_eq!(
+            extract_tags("#start and #Mixed-Case_1"),
+            vec!["start", "mixed-case_1"]
+        );
+        assert_eq!(extract_tags("#dup #DUP\n#dup"), vec!["dup"]);
+        assert!(extract_tags("## Header and #123 and issue#7").is_empty());
+        assert!(extract_tags("foo#bar (#paren) a#b").is_empty());
+    }
+
+    #[test]
+    fn tags_end_at_punctuation() {
+        assert_eq!(
+            extract_tags("Done #deploy. Then #ops, #qa!"),
+            vec!["deploy", "ops", "qa"]
+        );
+        assert_eq!(extract_tags("Ends with #dash-"), vec!["dash"]);
+        assert_eq!(extract_tags("#one#two"), vec!["one"]);
+    }
+
+    #[test]
+    fn help_is_cut_at_the_scissors_line() {
+        let content = format!("\n\nShipped it\n\n# Notes  \n\n# {}\n# Help\n", SCISSORS);
+        assert_eq!(strip_help(&content), "Shipped it\n\n# Notes");
+        assert_eq!(strip_help(&format!("# {}\nOnly help", SCISSORS)), "");
+        assert_eq!(strip_help("No help at all\n\n"), "No help at all");
+    }
+
+    #[test]
+    fn help_strips_carriage_returns_before_the_scissors() {
+        let content = format!("Title\r\n\r\nbody\r\n# {}\r\n# Help\r\n", SCISSORS);
+        assert_eq!(strip_help(&content), "Title\n\nbody");
+    }
+}
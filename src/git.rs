@@ -4,10 +4,14 @@
 //! including initialization, pulling, pushing, and automatic syncing
 //! of daily log files across devices.
 
-use crate::config::Config;
+use crate::{config::Config, lock::lock_log_dir};
 use chrono::Local;
 use std::{path::Path, process::Command};
 
+/// Pathspec for the daily log files at the top of the log directory, so
+/// templates and anything else kept beside the logs are never pushed.
+const LOG_FILES_PATHSPEC: &str = ":(glob)[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9].md";
+
 /// Checks if a directory is a git repository.
 ///
 /// Determines if the specified directory contains a `.git` subdirectory,
@@ -125,7 +129,8 @@ pub fn init_git_repo(log_dir: &str, repo_url: &str, branch: &str) -> anyhow::Res
 /// Pulls the latest logs from the remote git repository.
 ///
 /// Downloads and merges changes from the remote repository to keep
-/// the local logs synchronized.
+/// the local logs synchronized. The log directory is locked while pulling,
+/// so no log file is written halfway through a merge.
 ///
 /// # Arguments
 ///
@@ -136,6 +141,7 @@ pub fn init_git_repo(log_dir: &str, repo_url: &str, branch: &str) -> anyhow::Res
 ///
 /// Returns an error if:
 /// - The directory is not a git repository
+/// - The log directory cannot be locked
 /// - The pull operation fails
 ///
 /// # Example
@@ -152,6 +158,7 @@ pub fn git_pull(log_dir: &str, branch: &str) -> anyhow::Result<()> {
         ));
     }
 
+    let _lock = lock_log_dir(log_dir)?;
     println!("Pulling latest logs from git repository...");
     run_git_command(log_dir, &["pull", "origin", branch])?;
     println!("Successfully pulled latest logs.");
@@ -161,9 +168,11 @@ pub fn git_pull(log_dir: &str, branch: &str) -> anyhow::Result<()> {
 
 /// Pushes local log changes to the remote git repository.
 ///
-/// Adds all markdown files, creates a commit with a timestamp,
-/// and pushes to the remote repository. If there are no changes,
-/// the operation completes without creating a commit.
+/// Adds the daily log files (`YYYY-MM-DD.md`) at the top of the log
+/// directory, creates a commit with a timestamp, and pushes to the remote
+/// repository. Templates are never added. If there are no changes,
+/// the operation completes without creating a commit. The log directory
+/// is locked while pushing.
 ///
 /// # Arguments
 ///
@@ -174,6 +183,7 @@ pub fn git_pull(log_dir: &str, branch: &str) -> anyhow::Result<()> {
 ///
 /// Returns an error if:
 /// - The directory is not a git repository
+/// - The log directory cannot be locked
 /// - Any git operations fail
 ///
 /// # Example
@@ -190,16 +200,18 @@ pub fn git_push(log_dir: &str, branch: &str) -> anyhow::Result<()> {
         ));
     }
 
-    // Add all log files
-    run_git_command(log_dir, &["add", "*.md"])?;
+    let _lock = lock_log_dir(log_dir)?;
+
+    // Add the daily log files, including deletions
+    run_git_command(log_dir, &["add", "--", LOG_FILES_PATHSPEC])?;
 
-    // Check if there are changes to commit
-    let status_output = Command::new("git")
-        .args(&["status", "--porcelain"])
+    // Check if any log changes were staged
+    let staged = Command::new("git")
+        .args(["diff", "--cached", "--quiet"])
         .current_dir(log_dir)
-        .output()?;
+        .status()?;
 
-    if status_output.stdout.is_empty() {
+    if staged.success() {
         println!("No changes to push.");
         return Ok(());
     }
@@ -277,10 +289,11 @@ pub fn git_sync(config: &Config) -> anyhow::Result<()> {
 /// auto_sync_if_enabled(&config)?; // Only syncs if enabled in config
 /// ```
 pub fn auto_sync_if_enabled(config: &Config) -> anyhow::Result<()> {
-    if config.git_auto_sync.unwrap_or(false) && config.git_repo.is_some() {
-        if let Err(e) = git_sync(config) {
-            eprintln!("Warning: Auto-sync failed: {}", e);
-        }
+    if config.git_auto_sync.unwrap_or(false)
+        && config.git_repo.is_some()
+        && let Err(e) = git_sync(config)
+    {
+        eprintln!("Warning: Auto-sync failed: {}", e);
     }
     Ok(())
 }
\ No newline at end of file
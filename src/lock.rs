@@ -0,0 +1,144 @@
+//! File locking and atomic writes for log files.
+//!
+//! Every write to a day's log, and git sync, holds an advisory lock on the
+//! log directory, so a `dailylog -m` from a cron job and an edit session in
+//! another terminal cannot lose each other's entries. Writes only hold it
+//! while they read and replace a log, so sharing one lock between all days
+//! costs nothing, and the `.locks` folder only ever holds a single file.
+//!
+//! Writes go to a temporary file that is renamed over the log, so a crash
+//! mid-write never leaves a half-written log behind.
+
+use std::{
+    fs::{self, File, OpenOptions, TryLockError},
+    io::Write,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// Name of the lock file inside the `.locks` folder.
+const LOCK_FILE: &str = "dailylog.lock";
+
+/// A held lock, released when dropped.
+#[derive(Debug)]
+pub struct LogLock {
+    _file: File,
+}
+
+/// Returns the directory that holds lock files.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory where log files are stored
+pub fn locks_dir(log_dir: &Path) -> PathBuf {
+    log_dir.join(".locks")
+}
+
+/// Takes the lock on `log_dir`, telling the user if another process holds it.
+fn lock_dir(log_dir: &Path) -> anyhow::Result<LogLock> {
+    let dir = locks_dir(log_dir);
+    fs::create_dir_all(&dir)
+        .map_err(|e| anyhow::anyhow!("Failed to create lock directory {:?}: {}", dir, e))?;
+    let path = dir.join(LOCK_FILE);
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to open lock file {:?}: {}", path, e))?;
+
+    match file.try_lock() {
+        Ok(()) => {}
+        Err(TryLockError::WouldBlock) => {
+            eprintln!("Waiting for another dailylog process to finish with the log directory...");
+            file.lock()
+                .map_err(|e| anyhow::anyhow!("Failed to lock the log directory: {}", e))?;
+        }
+        Err(TryLockError::Error(e)) => {
+            return Err(anyhow::anyhow!("Failed to lock the log directory: {}", e));
+        }
+    }
+    Ok(LogLock { _file: file })
+}
+
+/// Locks a day's log file for writing.
+///
+/// Blocks until no other dailylog process is writing a log in the same
+/// directory or syncing it. Logs in one directory share a single lock, so
+/// holding it covers every day, as when moving an entry between days. The
+/// lock is released when the returned value is dropped.
+///
+/// # Arguments
+///
+/// * `path` - Path to the log file, inside the log directory
+///
+/// # Errors
+///
+/// Returns an error if the lock file cannot be created or locked.
+///
+/// # Example
+///
+/// ```rust
+/// use std::path::Path;
+/// use dailylog::lock::{lock_log_file, write_atomic};
+///
+/// let path = Path::new("/path/to/logs/2024-01-15.md");
+/// let _lock = lock_log_file(path)?;
+/// write_atomic(path, "## 09:00 - Standup\n\n")?;
+/// ```
+pub fn lock_log_file(path: &Path) -> anyhow::Result<LogLock> {
+    lock_dir(path.parent().unwrap_or(Path::new(".")))
+}
+
+/// Locks the whole log directory for git sync.
+///
+/// Waits for writes in progress to finish and blocks new ones until the
+/// returned value is dropped.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory where log files are stored
+///
+/// # Errors
+///
+/// Returns an error if the lock file cannot be created or locked.
+pub fn lock_log_dir(log_dir: &str) -> anyhow::Result<LogLock> {
+    lock_dir(Path::new(log_dir))
+}
+
+/// Replaces the content of `path` without ever leaving it half-written.
+///
+/// The content is written and flushed to a temporary file next to `path`,
+/// which is then renamed over it, keeping the file's permissions. Callers
+/// should hold the file's lock.
+///
+/// # Arguments
+///
+/// * `path` - The file to write
+/// * `content` - Its new content
+///
+/// # Errors
+///
+/// Returns an error if the temporary file cannot be written or renamed.
+pub fn write_atomic(path: &Path, content: &str) -> anyhow::Result<()> {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| anyhow::anyhow!("Invalid file path {:?}", path))?;
+    let temp = path.with_file_name(format!(".{}.{}.tmp", name, process::id()));
+
+    let written = (|| -> std::io::Result<()> {
+        let mut file = File::create(&temp)?;
+        if let Ok(metadata) = fs::metadata(path) {
+            file.set_permissions(metadata.permissions())?;
+        }
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temp, path)
+    })();
+
+    written.map_err(|e| {
+        fs::remove_file(&temp).ok();
+        anyhow::anyhow!("Failed to write {:?}: {}", path, e)
+    })
+}
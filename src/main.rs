@@ -18,40 +18,162 @@
 //! # Create a new log entry
 //! dailylog
 //!
+//! # Create an entry without opening an editor
+//! dailylog -m "Deployed v2" -m "Rolled out to all regions"
+//! echo "Nightly backup finished" | dailylog -
+//!
+//! # View today's log, any day, or a range of days
+//! dailylog show
+//! dailylog show friday
+//! dailylog show 2024-01-01..2024-01-07
+//! dailylog show --last 7
+//!
+//! # Start an entry from a template in ~/.dailylog/templates/
+//! dailylog -t standup
+//!
 //! # View previous day's log
 //! dailylog previous
 //!
 //! # Add to previous day's log
 //! dailylog yesterday
 //!
+//! # Target any other day
+//! dailylog --date "last friday"
+//! dailylog edit 2024-01-15
+//!
+//! # Edit a single entry of a day's log
+//! dailylog edit yesterday --entry 2
+//!
+//! # Delete, move or retime an entry, by number or HH:MM time
+//! dailylog entry rm 2
+//! dailylog entry mv 09:15 friday
+//! dailylog entry retime 1 08:45
+//!
 //! # Summarize past 7 days
 //! dailylog summary
 //!
+//! # Summarize a calendar week or month
+//! dailylog summary --week
+//! dailylog summary --month 2024-01
+//!
+//! # Summary as JSON for scripts and dashboards
+//! dailylog summary --format json
+//!
+//! # Calendar heatmap of this month or year
+//! dailylog calendar
+//! dailylog calendar --year
+//!
+//! # Standup note, or a digest of this week
+//! dailylog report
+//! dailylog report --week --bodies
+//!
+//! # Search all logs
+//! dailylog search "deploy" --ignore-case
+//!
+//! # List #hashtags, or summarize a single tag
+//! dailylog tags
+//! dailylog summary --tag oncall
+//!
+//! # Print a month of logs without the pager
+//! dailylog show --last 30 --no-pager
+//!
+//! # Browse logs in a full-screen interface
+//! dailylog tui
+//!
+//! # Force colors when piping
+//! dailylog summary --color always | less -R
+//!
+//! # Recover text from an interrupted editor session
+//! dailylog drafts
+//! dailylog drafts restore 1
+//!
+//! # Undo a deleted or replaced log
+//! dailylog trash
+//! dailylog trash restore 1
+//!
 //! # Git sync operations
 //! dailylog sync
 //! dailylog pull
 //! dailylog push
 //! ```
 
+mod calendar;
 mod config;
+mod date;
+mod daylog;
 mod display;
+mod draft;
 mod entry;
 mod git;
+mod lock;
+mod markdown;
+mod pager;
+mod report;
+mod search;
 mod summary;
+mod tags;
+mod template;
+mod theme;
+mod trash;
+mod tui;
 
-use clap::{Parser, Subcommand};
+use calendar::{HeatMetric, show_calendar};
+use clap::{CommandFactory, Parser, Subcommand, error::ErrorKind};
 use config::load_config;
-use display::{add_to_previous_day_log, view_previous_day_log};
-use entry::{append_to_log, get_log_file_path, open_editor, edit_today_log};
+use chrono::{Duration, NaiveTime};
+use date::{DateRange, configure_clock, parse_date, resolve_date, today};
+use display::{add_to_day_log, show_logs, view_day_log};
+use draft::{Draft, DraftKind, discard_drafts, restore_draft, show_drafts};
+use entry::{
+    DEFAULT_COMMENT_CHAR, append_to_log, confirm, edit_day_log, edit_entry, entry_from_messages,
+    entry_help, get_log_file_path_for_date, move_entry, open_editor, read_entry_from_stdin,
+    remove_entry, retime_entry,
+};
 use git::{auto_sync_if_enabled, git_pull, git_push, git_sync};
-use std::fs;
-use summary::summarize_logs;
+use pager::with_pager;
+use report::{ReportFormat, ReportOptions, digest_report, standup_report};
+use search::{SearchOptions, search_logs};
+use std::{
+    fs,
+    io::{self, IsTerminal, Write},
+};
+use summary::{OutputFormat, summarize_logs};
+use tags::list_tags;
+use template::entry_template;
+use theme::{ColorMode, Theme};
+use trash::{purge_trash, restore_from_trash, show_trash};
+use tui::run_tui;
 
 /// Command-line interface for dailylog
 #[derive(Parser)]
 #[command(name = "dailylog")]
 #[command(about = "A minimal journaling tool")]
 struct Cli {
+    /// Target date: YYYY-MM-DD, -3d, yesterday, friday or "last friday"
+    /// (for new entries, previous, show, yesterday, edit and entry)
+    #[arg(long, global = true, allow_hyphen_values = true)]
+    date: Option<String>,
+
+    /// Write the entry without an editor; repeat to add body paragraphs
+    #[arg(short, long = "message", value_name = "MESSAGE")]
+    message: Vec<String>,
+
+    /// Start new entries from this template in ~/.dailylog/templates/
+    #[arg(short, long, value_name = "NAME", conflicts_with = "message")]
+    template: Option<String>,
+
+    /// Never pipe long output through $PAGER
+    #[arg(long, global = true)]
+    no_pager: bool,
+
+    /// When to use colors (NO_COLOR is honored in auto mode)
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value_t = ColorMode::Auto)]
+    color: ColorMode,
+
+    /// Pass "-" to read the entry from stdin (implied when stdin is piped)
+    #[arg(value_name = "-")]
+    input: Option<String>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -59,17 +181,143 @@ struct Cli {
 /// Available subcommands for dailylog
 #[derive(Subcommand)]
 enum Commands {
-    /// View the previous day's log entry
+    /// View the previous day's log entry (or the --date day)
     Previous,
-    /// Add to the previous day's log entry
-    Yesterday,
-    /// Edit today's log entry in-place
-    Edit,
-    /// Summarize and review logs for the past X days
+    /// View the log for a day or range of days (default: the --date day or today)
+    Show {
+        /// A date (YYYY-MM-DD, -3d, friday, ...) or a range FROM..TO
+        #[arg(allow_hyphen_values = true, conflicts_with_all = ["today", "last"])]
+        range: Option<String>,
+        /// Show today's log
+        #[arg(long, conflicts_with = "last")]
+        today: bool,
+        /// Show the last N days, ending today
+        #[arg(short, long, value_name = "N")]
+        last: Option<u32>,
+    },
+    /// Add to the previous day's log entry (or the --date day)
+    Yesterday {
+        /// Start the entry from this template in ~/.dailylog/templates/
+        #[arg(short, long, value_name = "NAME")]
+        template: Option<String>,
+    },
+    /// Edit today's log (or another day's) in-place, or a single entry
+    Edit {
+        /// The day to edit (default: the --date day or today)
+        #[arg(value_name = "DATE", allow_hyphen_values = true)]
+        day: Option<String>,
+        /// Only edit one entry: its number (counting from 1) or its HH:MM time
+        #[arg(short, long, value_name = "N|HH:MM")]
+        entry: Option<String>,
+        /// Delete the log without asking if the editor is left empty
+        #[arg(long, conflicts_with = "entry")]
+        force: bool,
+    },
+    /// Summarize and review logs for the past X days or a calendar period
     Summary {
         /// Number of days to include in summary (default: 7)
         #[arg(short, long, default_value = "7")]
         days: u32,
+        /// Start of the period (default: --days before --to)
+        #[arg(long, allow_hyphen_values = true, conflicts_with_all = ["week", "month", "year"])]
+        from: Option<String>,
+        /// End of the period (default: today)
+        #[arg(long, allow_hyphen_values = true, conflicts_with_all = ["week", "month", "year"])]
+        to: Option<String>,
+        /// A calendar week: ISO week number, -N for N weeks ago, or this week
+        #[arg(long, value_name = "N", num_args = 0..=1, allow_negative_numbers = true, conflicts_with_all = ["month", "year"])]
+        week: Option<Option<String>>,
+        /// A calendar month: YYYY-MM, -N for N months ago, or this month
+        #[arg(long, value_name = "YYYY-MM", num_args = 0..=1, allow_negative_numbers = true, conflicts_with = "year")]
+        month: Option<Option<String>>,
+        /// A calendar year: YYYY, or this year
+        #[arg(long, value_name = "YYYY", num_args = 0..=1)]
+        year: Option<Option<String>>,
+        /// Only include entries tagged with this #hashtag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Output format; colour is disabled for non-text formats
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Show a calendar heatmap of logging activity
+    Calendar {
+        /// Month to show: YYYY-MM, -N for N months ago, or this month (default)
+        #[arg(long, value_name = "YYYY-MM", num_args = 0..=1, allow_negative_numbers = true, conflicts_with = "year")]
+        month: Option<Option<String>>,
+        /// Show a whole year: YYYY, or this year
+        #[arg(long, value_name = "YYYY", num_args = 0..=1)]
+        year: Option<Option<String>>,
+        /// Shade days by number of entries or words
+        #[arg(long, value_enum, default_value_t = HeatMetric::Entries)]
+        by: HeatMetric,
+    },
+    /// Generate a standup note, or a digest of a week, month or date range
+    Report {
+        /// Digest a calendar week: ISO week number, -N for N weeks ago, or this week
+        #[arg(long, value_name = "N", num_args = 0..=1, allow_negative_numbers = true, conflicts_with_all = ["month", "from", "to"])]
+        week: Option<Option<String>>,
+        /// Digest a calendar month: YYYY-MM, -N for N months ago, or this month
+        #[arg(long, value_name = "YYYY-MM", num_args = 0..=1, allow_negative_numbers = true, conflicts_with_all = ["from", "to"])]
+        month: Option<Option<String>>,
+        /// Digest from this date (default: 7 days before --to)
+        #[arg(long, allow_hyphen_values = true)]
+        from: Option<String>,
+        /// Digest up to this date (default: today)
+        #[arg(long, allow_hyphen_values = true)]
+        to: Option<String>,
+        /// Include entry bodies, not just titles
+        #[arg(short, long)]
+        bodies: bool,
+        /// Only include entries tagged with this #hashtag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Markdown)]
+        format: ReportFormat,
+    },
+    /// Search all logs for text or a regular expression
+    Search {
+        /// Text to search for (a regular expression with --regex)
+        #[arg(required_unless_present = "tag")]
+        query: Option<String>,
+        /// Treat the query as a regular expression
+        #[arg(short = 'e', long)]
+        regex: bool,
+        /// Match case-insensitively
+        #[arg(short, long)]
+        ignore_case: bool,
+        /// Only search logs on or after this date
+        #[arg(long, allow_hyphen_values = true)]
+        from: Option<String>,
+        /// Only search logs on or before this date
+        #[arg(long, allow_hyphen_values = true)]
+        to: Option<String>,
+        /// Only match entry titles
+        #[arg(short, long)]
+        title_only: bool,
+        /// Only match entries tagged with this #hashtag
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// List #hashtags with entry counts and last-used dates
+    Tags,
+    /// Browse, search and edit logs in a full-screen terminal interface
+    Tui,
+    /// List, restore or discard drafts left by interrupted editor sessions
+    Drafts {
+        #[command(subcommand)]
+        action: Option<DraftsAction>,
+    },
+    /// Remove, move or retime a single entry of today's log (or the --date day)
+    Entry {
+        #[command(subcommand)]
+        action: EntryAction,
+    },
+    /// List, restore or purge deleted and replaced logs
+    Trash {
+        #[command(subcommand)]
+        action: Option<TrashAction>,
     },
     /// Sync logs with git repository (pull then push)
     Sync,
@@ -79,6 +327,95 @@ enum Commands {
     Push,
 }
 
+impl Commands {
+    /// Returns `true` if the command works on a single day, so `--date` applies to it.
+    fn takes_date(&self) -> bool {
+        matches!(
+            self,
+            Commands::Previous
+                | Commands::Show { .. }
+                | Commands::Yesterday { .. }
+                | Commands::Edit { .. }
+                | Commands::Entry { .. }
+        )
+    }
+}
+
+/// Actions for the entry subcommand
+#[derive(Subcommand)]
+enum EntryAction {
+    /// Delete an entry (the log as it was is kept in the trash)
+    Rm {
+        /// The entry's number (counting from 1) or its HH:MM time
+        #[arg(value_name = "N|HH:MM")]
+        entry: String,
+    },
+    /// Move an entry logged on the wrong day to another day's log
+    Mv {
+        /// The entry's number (counting from 1) or its HH:MM time
+        #[arg(value_name = "N|HH:MM")]
+        entry: String,
+        /// The day to move it to: YYYY-MM-DD, -3d, yesterday, friday, ...
+        #[arg(allow_hyphen_values = true)]
+        to: String,
+    },
+    /// Change the time in an entry's header
+    Retime {
+        /// The entry's number (counting from 1) or its HH:MM time
+        #[arg(value_name = "N|HH:MM")]
+        entry: String,
+        /// The new time, as HH:MM
+        time: String,
+    },
+}
+
+/// Actions for the trash subcommand
+#[derive(Subcommand)]
+enum TrashAction {
+    /// List trashed logs, most recent first (the default)
+    List,
+    /// Put a trashed log back, trashing the day's current log if it has one
+    Restore {
+        /// The item's number in `dailylog trash`
+        number: usize,
+    },
+    /// Delete trashed logs for good
+    Purge {
+        /// The item's number in `dailylog trash`
+        #[arg(required_unless_present = "all")]
+        number: Option<usize>,
+        /// Empty the whole trash
+        #[arg(long, conflicts_with = "number")]
+        all: bool,
+        /// Empty the whole trash without asking
+        #[arg(long, requires = "all")]
+        force: bool,
+    },
+}
+
+/// Actions for the drafts subcommand
+#[derive(Subcommand)]
+enum DraftsAction {
+    /// List drafts, most recent first (the default)
+    List,
+    /// Save a draft to its day's log and delete it
+    Restore {
+        /// The draft's number in `dailylog drafts`
+        number: usize,
+    },
+    /// Delete a draft without saving it
+    Discard {
+        /// The draft's number in `dailylog drafts`
+        #[arg(required_unless_present = "all")]
+        number: Option<usize>,
+        /// Delete every draft
+        #[arg(long, conflicts_with = "number")]
+        all: bool,
+        /// Delete every draft without asking
+        #[arg(long, requires = "all")]
+        force: bool,
+    },
+}
 
 /// Main entry point for the dailylog application.
 ///
@@ -94,26 +431,264 @@ enum Commands {
 /// - The requested operation fails
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    if cli.date.is_some() && cli.command.as_ref().is_some_and(|command| !command.takes_date()) {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--date only applies to new entries and to previous, show, yesterday, edit and entry; \
+                 see the command's --help for its own date options",
+            )
+            .exit();
+    }
     let config = load_config()?;
+    configure_clock(&config)?;
     fs::create_dir_all(&config.log_dir)?;
+    let paging = !cli.no_pager && config.pager.unwrap_or(true);
+    let color = cli.color.choice();
+    let theme = Theme::from_config(&config.theme)?;
+    let comment_char = config.comment_char.as_deref().unwrap_or(DEFAULT_COMMENT_CHAR);
+
+    let target_date = resolve_date(cli.date.as_deref(), today())?;
+    let previous_date = resolve_date(cli.date.as_deref(), today() - Duration::days(1))?;
 
     match cli.command {
         Some(Commands::Previous) => {
-            view_previous_day_log(&config.log_dir)?;
+            with_pager(paging, color, |out| view_day_log(out, &config.log_dir, previous_date, &theme))?;
+        }
+        Some(Commands::Show {
+            range,
+            today: show_today,
+            last,
+        }) => {
+            let range = if show_today {
+                DateRange::day(today())
+            } else if let Some(days) = last {
+                DateRange::last_days(days, today())?
+            } else if let Some(range) = range {
+                DateRange::parse(&range, today())?
+            } else {
+                DateRange::day(target_date)
+            };
+            with_pager(paging, color, |out| show_logs(out, &config.log_dir, &range, &theme))?;
         }
-        Some(Commands::Yesterday) => {
-            add_to_previous_day_log(&config.log_dir)?;
+        Some(Commands::Yesterday { template }) => {
+            let template = entry_template(&config, template.as_deref(), previous_date)?;
+            add_to_day_log(
+                &config.log_dir,
+                previous_date,
+                &theme,
+                color,
+                template.as_deref(),
+                comment_char,
+                config.editor.as_deref(),
+            )?;
             auto_sync_if_enabled(&config)?;
         }
-        Some(Commands::Edit) => {
-            let log_path = get_log_file_path(&config.log_dir);
-            edit_today_log(&log_path)?;
-            println!("Log updated at {:?}", log_path);
+        Some(Commands::Edit { day, entry, force }) => {
+            let date = match day {
+                Some(_) if cli.date.is_some() => {
+                    return Err(anyhow::anyhow!(
+                        "Give the day to edit either as an argument or with --date, not both"
+                    ));
+                }
+                Some(day) => parse_date(&day, today())?,
+                None => target_date,
+            };
+            let log_path = get_log_file_path_for_date(&config.log_dir, date);
+            match entry {
+                Some(selector) => {
+                    if edit_entry(&config.log_dir, date, &selector, config.editor.as_deref())? {
+                        println!("Entry {} updated in {:?}", selector, log_path);
+                    } else {
+                        println!("Entry {} left unchanged.", selector);
+                    }
+                }
+                None => {
+                    let outcome = edit_day_log(&config.log_dir, date, config.editor.as_deref(), force)?;
+                    println!("{}", outcome.message(date, &log_path));
+                }
+            }
             auto_sync_if_enabled(&config)?;
         }
-        Some(Commands::Summary { days }) => {
-            summarize_logs(&config.log_dir, days, &config)?;
+        Some(Commands::Summary {
+            days,
+            from,
+            to,
+            week,
+            month,
+            year,
+            tag,
+            format,
+        }) => {
+            let range = if let Some(week) = week {
+                DateRange::week(week.as_deref(), today())?
+            } else if let Some(month) = month {
+                DateRange::month(month.as_deref(), today())?
+            } else if let Some(year) = year {
+                DateRange::year(year.as_deref(), today())?
+            } else if from.is_some() || to.is_some() {
+                DateRange::between(from.as_deref(), to.as_deref(), days, today())?
+            } else {
+                DateRange::last_days(days, today())?
+            };
+            with_pager(paging, color, |out| {
+                summarize_logs(
+                    out,
+                    &config.log_dir,
+                    &range,
+                    &config,
+                    tag.as_deref(),
+                    format,
+                    &theme,
+                )
+            })?;
+        }
+        Some(Commands::Search {
+            query,
+            regex,
+            ignore_case,
+            from,
+            to,
+            title_only,
+            tag,
+        }) => {
+            let options = SearchOptions {
+                query,
+                regex,
+                ignore_case,
+                from: from.map(|spec| parse_date(&spec, today())).transpose()?,
+                to: to.map(|spec| parse_date(&spec, today())).transpose()?,
+                title_only,
+                tag,
+            };
+            with_pager(paging, color, |out| search_logs(out, &config.log_dir, &options, &theme))?;
+        }
+        Some(Commands::Calendar { month, year, by }) => {
+            let range = match (month, year) {
+                (_, Some(year)) => DateRange::year(year.as_deref(), today())?,
+                (month, None) => DateRange::month(month.flatten().as_deref(), today())?,
+            };
+            with_pager(paging, color, |out| {
+                show_calendar(out, &config.log_dir, &range, &config, by, today(), &theme)
+            })?;
+        }
+        Some(Commands::Report {
+            week,
+            month,
+            from,
+            to,
+            bodies,
+            tag,
+            format,
+        }) => {
+            let options = ReportOptions {
+                bodies,
+                tag: tag.as_deref(),
+                format,
+            };
+            let range = if let Some(week) = week {
+                Some(DateRange::week(week.as_deref(), today())?)
+            } else if let Some(month) = month {
+                Some(DateRange::month(month.as_deref(), today())?)
+            } else if from.is_some() || to.is_some() {
+                Some(DateRange::between(from.as_deref(), to.as_deref(), 7, today())?)
+            } else {
+                None
+            };
+            let report = match range {
+                Some(range) => digest_report(&config.log_dir, &range, &options)?,
+                None => standup_report(&config.log_dir, &config, today(), &options)?,
+            };
+            with_pager(paging, color, |out| Ok(write!(out, "{}", report)?))?;
+        }
+        Some(Commands::Tags) => {
+            with_pager(paging, color, |out| list_tags(out, &config.log_dir, &theme))?;
+        }
+        Some(Commands::Tui) => {
+            run_tui(&config, &theme)?;
+        }
+        Some(Commands::Drafts { action }) => match action.unwrap_or(DraftsAction::List) {
+            DraftsAction::List => {
+                with_pager(paging, color, |out| show_drafts(out, &theme))?;
+            }
+            DraftsAction::Restore { number } => {
+                let log_path = restore_draft(&config.log_dir, number)?;
+                println!("Draft {} saved to {:?}", number, log_path);
+                auto_sync_if_enabled(&config)?;
+            }
+            DraftsAction::Discard { number, all, force } => {
+                if all
+                    && !force
+                    && io::stdin().is_terminal()
+                    && !confirm("Delete every draft without saving it?")?
+                {
+                    return Err(anyhow::anyhow!(
+                        "Nothing was discarded. Use --force to discard every draft without asking."
+                    ));
+                }
+                let (count, in_use) = discard_drafts(number)?;
+                println!("Discarded {} draft{}", count, if count == 1 { "" } else { "s" });
+                if in_use > 0 {
+                    println!(
+                        "Kept {} draft{} still open in an editor",
+                        in_use,
+                        if in_use == 1 { "" } else { "s" }
+                    );
+                }
+            }
+        },
+        Some(Commands::Entry { action }) => {
+            match action {
+                EntryAction::Rm { entry } => {
+                    let removed = remove_entry(&config.log_dir, target_date, &entry)?;
+                    println!(
+                        "Removed '{}' from {}. The log as it was is in `dailylog trash`.",
+                        removed.label(),
+                        target_date.format("%Y-%m-%d")
+                    );
+                }
+                EntryAction::Mv { entry, to } => {
+                    let to = parse_date(&to, today())?;
+                    let moved = move_entry(&config.log_dir, target_date, &entry, to)?;
+                    println!(
+                        "Moved '{}' from {} to {}",
+                        moved.label(),
+                        target_date.format("%Y-%m-%d"),
+                        to.format("%Y-%m-%d")
+                    );
+                }
+                EntryAction::Retime { entry, time } => {
+                    let time = NaiveTime::parse_from_str(time.trim(), "%H:%M")
+                        .map_err(|_| anyhow::anyhow!("Invalid time '{}': expected HH:MM", time))?;
+                    let retimed = retime_entry(&config.log_dir, target_date, &entry, time)?;
+                    println!("Changed entry to '{}'", retimed.label());
+                }
+            }
+            auto_sync_if_enabled(&config)?;
         }
+        Some(Commands::Trash { action }) => match action.unwrap_or(TrashAction::List) {
+            TrashAction::List => {
+                with_pager(paging, color, |out| show_trash(out, &config.log_dir, &theme))?;
+            }
+            TrashAction::Restore { number } => {
+                let log_path = restore_from_trash(&config.log_dir, number)?;
+                println!("Restored {:?} from the trash", log_path);
+                auto_sync_if_enabled(&config)?;
+            }
+            TrashAction::Purge { number, all, force } => {
+                if all
+                    && !force
+                    && io::stdin().is_terminal()
+                    && !confirm("Permanently delete every trashed log?")?
+                {
+                    return Err(anyhow::anyhow!(
+                        "Nothing was purged. Use --force to empty the trash without asking."
+                    ));
+                }
+                let count = purge_trash(&config.log_dir, number)?;
+                println!("Purged {} trashed log{}", count, if count == 1 { "" } else { "s" });
+            }
+        },
         Some(Commands::Sync) => {
             git_sync(&config)?;
         }
@@ -125,13 +700,42 @@ fn main() -> anyhow::Result<()> {
         }
         None => {
             // Default behavior: create new log entry
-            let log_path = get_log_file_path(&config.log_dir);
-            let entry = open_editor()?;
+            let log_path = get_log_file_path_for_date(&config.log_dir, target_date);
+            let mut draft = None;
+            let entry = match cli.input.as_deref() {
+                Some("-") => read_entry_from_stdin()?,
+                Some(other) => {
+                    return Err(anyhow::anyhow!(
+                        "Unexpected argument '{}'. Use -m \"message\" or \"-\" to read from stdin.",
+                        other
+                    ));
+                }
+                None if !cli.message.is_empty() => entry_from_messages(&cli.message),
+                None if !io::stdin().is_terminal() => read_entry_from_stdin()?,
+                None => {
+                    let template = entry_template(&config, cli.template.as_deref(), target_date)?;
+                    let help = entry_help(&config.log_dir, target_date, comment_char)?;
+                    let editor_draft = draft.insert(Draft::create(DraftKind::Entry, target_date)?);
+                    open_editor(
+                        editor_draft,
+                        template.as_deref(),
+                        &help,
+                        config.editor.as_deref(),
+                    )?
+                }
+            };
             if !entry.trim().is_empty() {
-                append_to_log(&log_path, &entry)?;
+                let save = || append_to_log(&log_path, &entry);
+                match draft {
+                    Some(draft) => draft.save_with(save)?,
+                    None => save()?,
+                }
                 println!("Log saved to {:?}", log_path);
                 auto_sync_if_enabled(&config)?;
             } else {
+                if let Some(draft) = draft {
+                    draft.discard()?;
+                }
                 println!("No content written. Aborted.");
             }
         }
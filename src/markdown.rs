@@ -0,0 +1,446 @@
+//! CommonMark rendering for the terminal.
+//!
+//! This module walks the events produced by `pulldown-cmark` and writes them
+//! as styled, word-wrapped text. Styling goes through `WriteColor`, so when
+//! color is off the same code produces readable plain text: bullets, list
+//! numbers, quote bars, task checkboxes and link targets are always written
+//! as text.
+
+use crate::theme::{Theme, layered};
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use termcolor::{ColorSpec, WriteColor};
+use unicode_width::UnicodeWidthStr;
+
+/// Renders markdown to `out`, wrapping paragraphs at `width` columns.
+///
+/// Supports headings, emphasis, strong and struck-through text, inline code,
+/// fenced and indented code blocks, links, images, ordered, unordered and
+/// nested lists, task list checkboxes, blockquotes and horizontal rules.
+/// Code blocks are never wrapped. HTML comments are hidden, as in a browser.
+///
+/// # Arguments
+///
+/// * `out` - Where to write the rendered text
+/// * `content` - The markdown to render
+/// * `width` - The column to wrap at, or `None` to never wrap
+/// * `theme` - Styles for headings, bullets, code, links and quotes
+///
+/// # Errors
+///
+/// Returns an error if writing to `out` fails.
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::markdown::render_markdown;
+/// use dailylog::theme::Theme;
+/// use termcolor::{ColorChoice, StandardStream};
+///
+/// let mut out = StandardStream::stdout(ColorChoice::Auto);
+/// let markdown = "- [x] Ship `v2`\n- [ ] Write *docs*";
+/// render_markdown(&mut out, markdown, Some(80), &Theme::default())?;
+/// ```
+pub fn render_markdown(
+    out: &mut impl WriteColor,
+    content: &str,
+    width: Option<usize>,
+    theme: &Theme,
+) -> anyhow::Result<()> {
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut renderer = Renderer::new(out, width, theme);
+    // HTML blocks are held back until they end, to drop those that are only comments
+    let mut html_block: Option<Vec<Event>> = None;
+    for event in Parser::new_ext(content, options) {
+        match (event, &mut html_block) {
+            (Event::InlineHtml(html), _) if is_comment(&html) => {}
+            (event @ Event::Start(Tag::HtmlBlock), _) => html_block = Some(vec![event]),
+            (event @ Event::End(TagEnd::HtmlBlock), Some(events)) => {
+                events.push(event);
+                let events = html_block.take().unwrap_or_default();
+                let comments_only = events.iter().all(|event| match event {
+                    Event::Html(html) => is_comment(html),
+                    _ => true,
+                });
+                if !comments_only {
+                    for event in events {
+                        renderer.event(event)?;
+                    }
+                }
+            }
+            (event, Some(events)) => events.push(event),
+            (event, None) => renderer.event(event)?,
+        }
+    }
+    renderer.end_line()
+}
+
+/// Returns `true` if `html` is a comment, such as `<!-- untitled -->`.
+fn is_comment(html: &str) -> bool {
+    let html = html.trim();
+    html.starts_with("<!--") && html.ends_with("-->")
+}
+
+/// A block that prefixes every line inside it, such as a list item or quote.
+struct Container {
+    /// Written at the start of the block's first line (e.g. a list bullet)
+    marker: Option<(String, ColorSpec)>,
+    /// Written at the start of every other line
+    indent: (String, ColorSpec),
+}
+
+/// Streaming state for rendering one markdown document.
+struct Renderer<'a, W> {
+    out: &'a mut W,
+    theme: &'a Theme,
+    /// Column to wrap at; `usize::MAX` disables wrapping
+    width: usize,
+    /// Whether `out` shows colors; inline code gets backticks when it doesn't
+    color: bool,
+    /// Styles of the enclosing inline and heading elements, innermost last
+    styles: Vec<ColorSpec>,
+    /// Enclosing list items and blockquotes, outermost first
+    containers: Vec<Container>,
+    /// Next number for each enclosing list, `None` for bullet lists
+    lists: Vec<Option<u64>>,
+    /// Destination and text seen so far for each enclosing link or image
+    links: Vec<(String, String)>,
+    /// Text of the code block being read, if any
+    code_block: Option<String>,
+    /// Whether the container prefixes have been written for the current line
+    line_started: bool,
+    /// Whether the current line has content after its prefixes
+    line_has_text: bool,
+    /// The current display column
+    column: usize,
+    /// Whether a space is due before the next word
+    pending_space: bool,
+    /// The style the pending space was written in
+    space_style: ColorSpec,
+    /// The style last applied to `out`, to avoid repeating escape codes
+    applied_style: Option<ColorSpec>,
+    /// Whether a blank line is due before the next block
+    pending_blank: bool,
+    /// Whether anything has been written yet
+    wrote_anything: bool,
+}
+
+impl<'a, W: WriteColor> Renderer<'a, W> {
+    fn new(out: &'a mut W, width: Option<usize>, theme: &'a Theme) -> Self {
+        let color = out.supports_color();
+        Renderer {
+            out,
+            theme,
+            width: width.unwrap_or(usize::MAX),
+            color,
+            styles: Vec::new(),
+            containers: Vec::new(),
+            lists: Vec::new(),
+            links: Vec::new(),
+            code_block: None,
+            line_started: false,
+            line_has_text: false,
+            column: 0,
+            pending_space: false,
+            space_style: ColorSpec::new(),
+            applied_style: None,
+            pending_blank: false,
+            wrote_anything: false,
+        }
+    }
+
+    /// Handles a single parser event.
+    fn event(&mut self, event: Event) -> anyhow::Result<()> {
+        match event {
+            Event::Start(tag) => self.start(tag)?,
+            Event::End(tag) => self.end(tag)?,
+            Event::Text(text) => match &mut self.code_block {
+                Some(code) => code.push_str(&text),
+                None => self.text(&text)?,
+            },
+            Event::Code(code) => {
+                let code = if self.color {
+                    code.to_string()
+                } else {
+                    format!("`{}`", code)
+                };
+                self.styles.push(self.theme.code.clone());
+                self.text(&code)?;
+                self.styles.pop();
+            }
+            Event::Html(html) | Event::InlineHtml(html) => self.text(&html)?,
+            Event::FootnoteReference(label) => self.text(&format!("[^{}]", label))?,
+            Event::SoftBreak => self.space(),
+            Event::HardBreak => self.end_line()?,
+            Event::Rule => {
+                self.start_block()?;
+                self.start_line()?;
+                let length = self.width.min(80).saturating_sub(self.column).max(3);
+                let spec = self.theme.muted.clone();
+                self.write_styled(&"─".repeat(length), &spec)?;
+                self.end_block()?;
+            }
+            Event::TaskListMarker(checked) => {
+                let spec = if checked {
+                    self.theme.bullet.clone()
+                } else {
+                    ColorSpec::new()
+                };
+                self.styles.push(spec);
+                self.word(if checked { "[x]" } else { "[ ]" })?;
+                self.styles.pop();
+                self.space();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handles the start of a block or inline element.
+    fn start(&mut self, tag: Tag) -> anyhow::Result<()> {
+        match tag {
+            Tag::Paragraph | Tag::HtmlBlock => self.start_block()?,
+            Tag::Heading { level, .. } => {
+                self.start_block()?;
+                let spec = match level {
+                    HeadingLevel::H1 => &self.theme.heading,
+                    HeadingLevel::H2 => &self.theme.entry_title,
+                    _ => &self.theme.subheading,
+                };
+                self.styles.push(spec.clone());
+                self.text(&"#".repeat(level as usize))?;
+                self.space();
+            }
+            Tag::BlockQuote(_) => {
+                self.start_block()?;
+                self.containers.push(Container {
+                    marker: None,
+                    indent: ("│ ".to_string(), self.theme.muted.clone()),
+                });
+            }
+            Tag::CodeBlock(_) => {
+                self.start_block()?;
+                self.code_block = Some(String::new());
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.start_block()?;
+                } else {
+                    self.end_line()?;
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.end_line()?;
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "• ".to_string(),
+                };
+                let indent = " ".repeat(marker.width());
+                self.containers.push(Container {
+                    marker: Some((marker, self.theme.bullet.clone())),
+                    indent: (indent, ColorSpec::new()),
+                });
+            }
+            Tag::Emphasis => self.styles.push(ColorSpec::new().set_italic(true).clone()),
+            Tag::Strong => self.styles.push(ColorSpec::new().set_bold(true).clone()),
+            Tag::Strikethrough => self
+                .styles
+                .push(ColorSpec::new().set_strikethrough(true).clone()),
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.styles.push(self.theme.link.clone());
+                self.links.push((dest_url.to_string(), String::new()));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handles the end of a block or inline element.
+    fn end(&mut self, tag: TagEnd) -> anyhow::Result<()> {
+        match tag {
+            TagEnd::Paragraph | TagEnd::HtmlBlock => self.end_block()?,
+            TagEnd::Heading(_) => {
+                self.styles.pop();
+                self.end_block()?;
+            }
+            TagEnd::BlockQuote(_) => {
+                self.end_line()?;
+                self.containers.pop();
+                self.pending_blank = true;
+            }
+            TagEnd::CodeBlock => {
+                let code = self.code_block.take().unwrap_or_default();
+                for line in code.trim_end_matches('\n').lines() {
+                    self.start_line()?;
+                    self.write_styled("  ", &ColorSpec::new())?;
+                    let spec = self.theme.code.clone();
+                    self.write_styled(line, &spec)?;
+                    self.end_line()?;
+                }
+                self.end_block()?;
+            }
+            TagEnd::List(_) => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.end_block()?;
+                }
+            }
+            TagEnd::Item => {
+                // Empty items still show their bullet
+                if self.containers.last().is_some_and(|item| item.marker.is_some()) {
+                    self.start_line()?;
+                }
+                self.end_line()?;
+                self.containers.pop();
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            TagEnd::Link | TagEnd::Image => {
+                self.styles.pop();
+                if let Some((url, text)) = self.links.pop()
+                    && !url.is_empty()
+                    && url != text
+                {
+                    self.space();
+                    self.styles.push(self.theme.muted.clone());
+                    self.text(&format!("({})", url))?;
+                    self.styles.pop();
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Writes inline text, wrapping between words.
+    fn text(&mut self, text: &str) -> anyhow::Result<()> {
+        if let Some((_, link_text)) = self.links.last_mut() {
+            link_text.push_str(text);
+        }
+        if text.starts_with(char::is_whitespace) {
+            self.space();
+        }
+
+        let mut words = text.split_whitespace().peekable();
+        while let Some(word) = words.next() {
+            self.word(word)?;
+            if words.peek().is_some() {
+                self.space();
+            }
+        }
+
+        if text.ends_with(char::is_whitespace) {
+            self.space();
+        }
+        Ok(())
+    }
+
+    /// Marks a space as due before the next word, in the current style.
+    fn space(&mut self) {
+        if !self.pending_space {
+            self.pending_space = true;
+            self.space_style = self.current_style();
+        }
+    }
+
+    /// Writes a single word, starting a new line first if it would not fit.
+    fn word(&mut self, word: &str) -> anyhow::Result<()> {
+        self.start_line()?;
+        let space = usize::from(self.pending_space && self.line_has_text);
+        if self.line_has_text && self.column + space + word.width() > self.width {
+            self.end_line()?;
+            self.start_line()?;
+        } else if space == 1 {
+            let spec = self.space_style.clone();
+            self.write_styled(" ", &spec)?;
+        }
+
+        let spec = self.current_style();
+        self.write_styled(word, &spec)?;
+        self.line_has_text = true;
+        self.pending_space = false;
+        Ok(())
+    }
+
+    /// Writes the container prefixes if the current line has none yet.
+    fn start_line(&mut self) -> anyhow::Result<()> {
+        if self.line_started {
+            return Ok(());
+        }
+        self.line_started = true;
+
+        let mut prefixes = Vec::new();
+        for container in &mut self.containers {
+            prefixes.push(container.marker.take().unwrap_or_else(|| container.indent.clone()));
+        }
+        for (prefix, spec) in prefixes {
+            self.write_styled(&prefix, &spec)?;
+        }
+        Ok(())
+    }
+
+    /// Ends the current line, if one has been started.
+    fn end_line(&mut self) -> anyhow::Result<()> {
+        if self.line_started {
+            self.out.reset()?;
+            self.applied_style = None;
+            writeln!(self.out)?;
+            self.line_started = false;
+            self.line_has_text = false;
+            self.column = 0;
+        }
+        self.pending_space = false;
+        Ok(())
+    }
+
+    /// Prepares for a new block, separating it from the previous one.
+    fn start_block(&mut self) -> anyhow::Result<()> {
+        self.end_line()?;
+        if self.pending_blank && self.wrote_anything {
+            let indents: Vec<_> = self
+                .containers
+                .iter()
+                .map(|container| container.indent.clone())
+                .collect();
+            for (indent, spec) in indents {
+                self.out.set_color(&spec)?;
+                write!(self.out, "{}", indent.trim_end())?;
+            }
+            self.out.reset()?;
+            self.applied_style = None;
+            writeln!(self.out)?;
+        }
+        self.pending_blank = false;
+        Ok(())
+    }
+
+    /// Finishes a block, so the next one is preceded by a blank line.
+    fn end_block(&mut self) -> anyhow::Result<()> {
+        self.end_line()?;
+        self.pending_blank = true;
+        Ok(())
+    }
+
+    /// Combines the enclosing styles into one color specification.
+    fn current_style(&self) -> ColorSpec {
+        self.styles
+            .iter()
+            .fold(ColorSpec::new(), |spec, layer| layered(&spec, layer))
+    }
+
+    /// Writes text in the given style and advances the column.
+    fn write_styled(&mut self, text: &str, spec: &ColorSpec) -> anyhow::Result<()> {
+        if self.applied_style.as_ref() != Some(spec) {
+            self.out.set_color(spec)?;
+            self.applied_style = Some(spec.clone());
+        }
+        write!(self.out, "{}", text)?;
+        self.column += text.width();
+        self.wrote_anything = true;
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+//! Paging of long terminal output.
+//!
+//! Commands that can print more than a screenful render into an in-memory
+//! buffer first. If stdout is a terminal and the output is taller than it,
+//! the buffer is piped through `$PAGER` (default `less -R`) so colors are
+//! preserved; otherwise it is written to stdout as usual.
+
+use regex::Regex;
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+    process::{Command, Stdio},
+    sync::LazyLock,
+};
+use terminal_size::{Height, Width, terminal_size};
+use termcolor::{Buffer, BufferWriter, ColorChoice};
+
+/// The pager used when `$PAGER` is not set.
+const DEFAULT_PAGER: &str = "less -R";
+
+/// Matches ANSI escape sequences, which take up no space on screen.
+static ANSI_ESCAPE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").expect("valid ANSI pattern"));
+
+/// Renders output into a buffer and shows it through a pager if it is too long.
+///
+/// The pager is only used when `enabled` is set, stdout is a terminal and
+/// the rendered output has more lines than the terminal is tall (taking line
+/// wrapping into account). If the pager cannot be started, the output is
+/// written to stdout instead.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether paging is allowed (from `--no-pager` and the `pager` config option)
+/// * `color` - Whether the rendered output may contain colors
+/// * `render` - Writes the command's output into the given buffer
+///
+/// # Errors
+///
+/// Returns an error if `render` fails or the output cannot be written.
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::pager::with_pager;
+/// use std::io::Write;
+/// use termcolor::ColorChoice;
+///
+/// with_pager(true, ColorChoice::Auto, |out| {
+///     for i in 0..500 {
+///         writeln!(out, "line {}", i)?;
+///     }
+///     Ok(())
+/// })?;
+/// ```
+pub fn with_pager<F>(enabled: bool, color: ColorChoice, render: F) -> anyhow::Result<()>
+where
+    F: FnOnce(&mut Buffer) -> anyhow::Result<()>,
+{
+    let writer = BufferWriter::stdout(color);
+    let mut buffer = writer.buffer();
+    render(&mut buffer)?;
+
+    if enabled
+        && io::stdout().is_terminal()
+        && exceeds_terminal(&String::from_utf8_lossy(buffer.as_slice()))
+        && run_pager(buffer.as_slice())?
+    {
+        return Ok(());
+    }
+
+    writer.print(&buffer)?;
+    Ok(())
+}
+
+/// Returns true if `output` needs more rows than the terminal has.
+fn exceeds_terminal(output: &str) -> bool {
+    let Some((Width(width), Height(height))) = terminal_size() else {
+        return false;
+    };
+    let width = width.max(1) as usize;
+
+    let rows: usize = output
+        .lines()
+        .map(|line| {
+            let visible = ANSI_ESCAPE.replace_all(line, "").chars().count();
+            visible.div_ceil(width).max(1)
+        })
+        .sum();
+    rows >= height as usize
+}
+
+/// Pipes `output` into the user's pager and waits for it to exit.
+///
+/// `$PAGER` is split shell-style, like the editor command, so quoted paths
+/// and arguments work.
+///
+/// Returns `Ok(false)` if paging is disabled by an empty `$PAGER` or `cat`,
+/// or if the pager cannot be parsed or started, so the caller can fall back
+/// to stdout.
+fn run_pager(output: &[u8]) -> anyhow::Result<bool> {
+    let pager = env::var("PAGER").unwrap_or_else(|_| DEFAULT_PAGER.to_string());
+    let Ok(parts) = shell_words::split(&pager) else {
+        return Ok(false);
+    };
+    let Some((program, args)) = parts.split_first() else {
+        return Ok(false);
+    };
+    if program == "cat" {
+        return Ok(false);
+    }
+
+    let mut command = Command::new(program);
+    command.args(args).stdin(Stdio::piped());
+    // Like git, let less pass colors through and quit on short output
+    if env::var_os("LESS").is_none() {
+        command.env("LESS", "FRX");
+    }
+
+    let Ok(mut child) = command.spawn() else {
+        return Ok(false);
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The user may quit the pager before reading everything
+        match stdin.write_all(output) {
+            Err(e) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e.into()),
+            _ => {}
+        }
+    }
+    child.wait()?;
+
+    Ok(true)
+}
@@ -0,0 +1,214 @@
+//! Standup and digest report generation.
+//!
+//! This module turns log entries into ready-to-paste reports: a
+//! "Yesterday / Today" standup note, or a digest of every logged day in a
+//! date range such as a calendar week.
+
+use crate::{
+    config::Config,
+    date::{DateRange, parse_weekday},
+    daylog::{DayLog, Entry},
+    summary::extract_entry_titles,
+};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use clap::ValueEnum;
+
+/// Output format for reports.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// Markdown with headings, for wikis, issues and email
+    #[default]
+    Markdown,
+    /// Plain text, for chat messages
+    Text,
+}
+
+/// Options shared by all report kinds.
+pub struct ReportOptions<'a> {
+    /// Include entry bodies below their titles
+    pub bodies: bool,
+    /// Only include entries tagged with this hashtag
+    pub tag: Option<&'a str>,
+    /// How to format the report
+    pub format: ReportFormat,
+}
+
+/// Renders a "Yesterday / Today" standup report.
+///
+/// "Yesterday" is the most recent day before `today` that is one of the
+/// configured `summary_days`, so on a Monday it covers Friday when only
+/// weekdays are configured.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory containing log files
+/// * `config` - Application configuration containing summary day filters
+/// * `today` - The day the standup is for
+/// * `options` - Formatting and filtering options
+///
+/// # Errors
+///
+/// Returns an error if log files cannot be read.
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::config::load_config;
+/// use dailylog::date::today;
+/// use dailylog::report::{standup_report, ReportFormat, ReportOptions};
+///
+/// let config = load_config()?;
+/// let options = ReportOptions { bodies: false, tag: None, format: ReportFormat::Text };
+/// println!("{}", standup_report(&config.log_dir, &config, today(), &options)?);
+/// ```
+pub fn standup_report(
+    log_dir: &str,
+    config: &Config,
+    today: NaiveDate,
+    options: &ReportOptions,
+) -> anyhow::Result<String> {
+    let previous = previous_working_day(today, config);
+    let sections = [
+        ("Yesterday", load_entries(log_dir, previous, options.tag)?, previous),
+        ("Today", load_entries(log_dir, today, options.tag)?, today),
+    ];
+
+    let mut report = match options.format {
+        ReportFormat::Markdown => format!("## Standup - {}\n", format_day(today)),
+        ReportFormat::Text => format!("Standup - {}\n", format_day(today)),
+    };
+
+    for (heading, entries, date) in sections {
+        match options.format {
+            ReportFormat::Markdown => {
+                report.push_str(&format!("\n**{}** ({})\n\n", heading, format_day(date)));
+            }
+            ReportFormat::Text => {
+                report.push_str(&format!("\n{} ({}):\n", heading, format_day(date)));
+            }
+        }
+        if entries.is_empty() {
+            report.push_str("- Nothing logged\n");
+        } else {
+            render_entries(&mut report, &entries, options.bodies);
+        }
+    }
+
+    Ok(report)
+}
+
+/// Renders a digest of every logged day in a date range, oldest first.
+///
+/// Days without entries are left out.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory containing log files
+/// * `range` - The days to include; its label becomes the report heading
+/// * `options` - Formatting and filtering options
+///
+/// # Errors
+///
+/// Returns an error if log files cannot be read.
+pub fn digest_report(
+    log_dir: &str,
+    range: &DateRange,
+    options: &ReportOptions,
+) -> anyhow::Result<String> {
+    let mut report = match options.format {
+        ReportFormat::Markdown => format!("# {}\n", range.label),
+        ReportFormat::Text => format!("{}\n", range.label),
+    };
+
+    let mut found_entries = false;
+    for date in range.start.iter_days().take_while(|date| *date <= range.end) {
+        let entries = load_entries(log_dir, date, options.tag)?;
+        if entries.is_empty() {
+            continue;
+        }
+        found_entries = true;
+
+        match options.format {
+            ReportFormat::Markdown => report.push_str(&format!("\n## {}\n\n", format_day(date))),
+            ReportFormat::Text => report.push_str(&format!("\n{}:\n", format_day(date))),
+        }
+        render_entries(&mut report, &entries, options.bodies);
+    }
+
+    if !found_entries {
+        report.push_str("\nNothing logged.\n");
+    }
+
+    Ok(report)
+}
+
+/// Returns the most recent configured summary day before `today`.
+///
+/// Falls back to the calendar day before `today` if no summary days are
+/// configured.
+///
+/// # Arguments
+///
+/// * `today` - The day to look back from
+/// * `config` - Application configuration containing summary day filters
+pub fn previous_working_day(today: NaiveDate, config: &Config) -> NaiveDate {
+    let allowed_weekdays: Vec<Weekday> = config
+        .summary_days
+        .iter()
+        .filter_map(|day| parse_weekday(day))
+        .collect();
+
+    (1..=7)
+        .map(|days_back| today - Duration::days(days_back))
+        .find(|date| allowed_weekdays.contains(&date.weekday()))
+        .unwrap_or(today - Duration::days(1))
+}
+
+/// Loads a day's entries, keeping only those with `tag` if one is given.
+fn load_entries(log_dir: &str, date: NaiveDate, tag: Option<&str>) -> anyhow::Result<Vec<Entry>> {
+    let mut day = DayLog::load(log_dir, date)?;
+    day.entries
+        .retain(|entry| tag.is_none_or(|tag| entry.has_tag(tag)));
+    Ok(day.entries)
+}
+
+/// Appends entries to a report as a bullet list.
+///
+/// Without bodies, each entry contributes the titles `extract_entry_titles`
+/// finds in it, and untitled entries are listed by their first line, like
+/// `Entry::label`. With bodies, each entry's body is indented below its
+/// bullet.
+fn render_entries(report: &mut String, entries: &[Entry], bodies: bool) {
+    for entry in entries {
+        if !bodies {
+            let titles = extract_entry_titles(std::slice::from_ref(entry));
+            if titles.is_empty() {
+                report.push_str(&format!("- {}\n", entry.label().trim()));
+            }
+            for title in titles {
+                report.push_str(&format!("- {}\n", title));
+            }
+            continue;
+        }
+
+        let mut lines = entry.body.lines();
+        let bullet = match &entry.title {
+            Some(title) => title.as_str(),
+            None => lines.next().unwrap_or_default().trim(),
+        };
+        report.push_str(&format!("- {}\n", bullet));
+
+        for line in lines {
+            if line.trim().is_empty() {
+                report.push('\n');
+            } else {
+                report.push_str(&format!("  {}\n", line));
+            }
+        }
+    }
+}
+
+/// Formats a date as e.g. `Monday 2024-01-15`.
+fn format_day(date: NaiveDate) -> String {
+    date.format("%A %Y-%m-%d").to_string()
+}
@@ -0,0 +1,244 @@
+//! Full-text search across daily logs.
+//!
+//! This module walks every `YYYY-MM-DD.md` file in the log directory,
+//! matches the query against individual entries, and prints the hits
+//! grouped by date with the matching text highlighted.
+
+use crate::{
+    display::write_highlighted,
+    daylog::DayLog,
+    entry::list_log_dates,
+    theme::Theme,
+};
+use chrono::NaiveDate;
+use regex::{Regex, RegexBuilder};
+use termcolor::{ColorSpec, WriteColor};
+
+/// Options controlling how logs are searched.
+pub struct SearchOptions {
+    /// The text or regular expression to search for; `None` matches every entry
+    pub query: Option<String>,
+    /// Whether `query` is a regular expression rather than literal text
+    pub regex: bool,
+    /// Whether matching ignores case
+    pub ignore_case: bool,
+    /// Only search logs on or after this date
+    pub from: Option<NaiveDate>,
+    /// Only search logs on or before this date
+    pub to: Option<NaiveDate>,
+    /// Only match against entry titles, not bodies
+    pub title_only: bool,
+    /// Only match entries carrying this hashtag
+    pub tag: Option<String>,
+}
+
+/// Builds the regular expression used to match log content.
+///
+/// Without a query, the pattern matches the tag itself, so that the lines
+/// mentioning it are shown and highlighted.
+///
+/// # Errors
+///
+/// Returns an error if `options.regex` is set and the query is not a valid
+/// regular expression.
+fn build_pattern(options: &SearchOptions) -> anyhow::Result<Regex> {
+    let (pattern, ignore_case) = match (&options.query, &options.tag) {
+        (Some(query), _) if options.regex => (query.clone(), options.ignore_case),
+        (Some(query), _) => (regex::escape(query), options.ignore_case),
+        (None, Some(tag)) => {
+            let tag = regex::escape(tag.trim_start_matches('#'));
+            (format!(r"#{}\b", tag), true)
+        }
+        (None, None) => return Err(anyhow::anyhow!("Nothing to search for: give a query or --tag")),
+    };
+
+    RegexBuilder::new(&pattern)
+        .case_insensitive(ignore_case)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Invalid search pattern: {}", e))
+}
+
+/// Searches all logs and prints matching entries grouped by date.
+///
+/// Logs are searched from most recent to oldest. For each matching entry
+/// the entry header is shown, followed by the matching body lines (unless
+/// searching titles only), with every match highlighted. When a tag is given,
+/// only entries carrying it are considered; with a tag and no query, every
+/// tagged entry is listed.
+///
+/// # Arguments
+///
+/// * `out` - Where to write the results
+/// * `log_dir` - The directory containing log files
+/// * `options` - The query and filters to apply
+/// * `theme` - The styles to use
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The query is not a valid regular expression
+/// - The log directory or a log file cannot be read
+/// - Terminal output fails
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::search::{search_logs, SearchOptions};
+/// use dailylog::theme::Theme;
+/// use termcolor::{ColorChoice, StandardStream};
+///
+/// let mut out = StandardStream::stdout(ColorChoice::Auto);
+/// search_logs(&mut out, "/path/to/logs", &SearchOptions {
+///     query: Some("deploy".to_string()),
+///     regex: false,
+///     ignore_case: true,
+///     from: None,
+///     to: None,
+///     title_only: false,
+///     tag: Some("oncall".to_string()),
+/// }, &Theme::default())?;
+/// ```
+pub fn search_logs(
+    out: &mut impl WriteColor,
+    log_dir: &str,
+    options: &SearchOptions,
+    theme: &Theme,
+) -> anyhow::Result<()> {
+    let pattern = build_pattern(options)?;
+
+    let mut matching_entries = 0;
+    let mut matching_days = 0;
+
+    let dates = list_log_dates(log_dir)?;
+    for date in dates.into_iter().rev() {
+        if options.from.is_some_and(|from| date < from) || options.to.is_some_and(|to| date > to) {
+            continue;
+        }
+
+        let day = DayLog::load(log_dir, date)?;
+        let mut printed_date = false;
+
+        for entry in &day.entries {
+            if options.tag.as_ref().is_some_and(|tag| !entry.has_tag(tag)) {
+                continue;
+            }
+
+            let header = entry.header();
+            let header_matches = header.as_deref().is_some_and(|header| pattern.is_match(header));
+            let body_matches: Vec<&str> = if options.title_only {
+                Vec::new()
+            } else {
+                entry
+                    .body
+                    .lines()
+                    .filter(|line| pattern.is_match(line))
+                    .collect()
+            };
+
+            if !header_matches && body_matches.is_empty() {
+                continue;
+            }
+
+            if !printed_date {
+                out.set_color(&theme.date_header)?;
+                writeln!(out, "\n--- {} ---", date.format("%Y-%m-%d (%A)"))?;
+                out.reset()?;
+                printed_date = true;
+                matching_days += 1;
+            }
+            matching_entries += 1;
+
+            match header {
+                Some(header) => {
+                    write_highlighted(out, &header, &pattern, &theme.entry_title, &theme.highlight)?
+                }
+                None => {
+                    out.set_color(&theme.entry_title)?;
+                    writeln!(out, "(untitled entry)")?;
+                    out.reset()?;
+                }
+            }
+            for line in body_matches {
+                write!(out, "  ")?;
+                write_highlighted(out, line.trim(), &pattern, &ColorSpec::new(), &theme.highlight)?;
+            }
+        }
+    }
+
+    if matching_entries == 0 {
+        match (&options.query, &options.tag) {
+            (Some(query), _) => writeln!(out, "No entries found matching '{}'.", query)?,
+            (None, tag) => writeln!(
+                out,
+                "No entries found tagged #{}.",
+                tag.as_deref().unwrap_or_default().trim_start_matches('#')
+            )?,
+        }
+    } else {
+        writeln!(
+            out,
+            "\nFound {} matching entries across {} days.",
+            matching_entries, matching_days
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(query: Option<&str>, tag: Option<&str>) -> SearchOptions {
+        SearchOptions {
+            query: query.map(str::to_string),
+            regex: false,
+            ignore_case: false,
+            from: None,
+            to: None,
+            title_only: false,
+            tag: tag.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn literal_queries_are_escaped() {
+        let pattern = build_pattern(&options(Some("v1.2 (beta)"), None)).unwrap();
+        assert!(pattern.is_match("Shipped v1.2 (beta) today"));
+        assert!(!pattern.is_match("Shipped v1x2 (beta) today"));
+    }
+
+    #[test]
+    fn regex_queries_are_used_as_is() {
+        let mut options = options(Some("deploy(ed|ing)"), None);
+        options.regex = true;
+        assert!(build_pattern(&options).unwrap().is_match("deployed"));
+
+        options.query = Some("(unclosed".to_string());
+        assert!(build_pattern(&options).is_err());
+    }
+
+    #[test]
+    fn case_is_ignored_only_when_asked() {
+        let mut options = options(Some("Deploy"), None);
+        assert!(!build_pattern(&options).unwrap().is_match("deploy"));
+        options.ignore_case = true;
+        assert!(build_pattern(&options).unwrap().is_match("DEPLOY"));
+    }
+
+    #[test]
+    fn a_tag_alone_matches_the_whole_tag() {
+        let pattern = build_pattern(&options(None, Some("#OnCall"))).unwrap();
+        assert!(pattern.is_match("Paged for #oncall."));
+        assert!(!pattern.is_match("Paged for #oncaller"));
+        assert!(!pattern.is_match("oncall"));
+    }
+
+    #[test]
+    fn a_query_takes_precedence_over_the_tag() {
+        let pattern = build_pattern(&options(Some("deploy"), Some("ops"))).unwrap();
+        assert!(pattern.is_match("deploy"));
+        assert!(!pattern.is_match("#ops"));
+        assert!(build_pattern(&options(None, None)).is_err());
+    }
+}
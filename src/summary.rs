@@ -2,83 +2,245 @@
 //!
 //! This module provides functionality for analyzing daily logs over time,
 //! generating statistics about logging consistency, and displaying
-//! summaries with colorized output.
+//! summaries with colorized output or in machine-readable formats.
 
-use crate::{config::Config, entry::get_log_file_path_for_date};
-use chrono::{Datelike, Duration, Local, Weekday};
-use std::{fs, io::Write};
-use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+use crate::{
+    config::Config,
+    date::{DateRange, parse_weekday, today},
+    daylog::{DayLog, Entry},
+    entry::list_log_dates,
+    theme::Theme,
+};
+use chrono::{Datelike, NaiveDate, Timelike, Weekday};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use termcolor::WriteColor;
 
-/// Parses a day string into a `Weekday` enum.
-///
-/// Accepts both full day names and three-letter abbreviations,
-/// case-insensitive.
-///
-/// # Arguments
-///
-/// * `day_str` - The day string to parse (e.g., "monday", "Mon", "tue")
-///
-/// # Returns
-///
-/// `Some(Weekday)` if the string is recognized, `None` otherwise.
-///
-/// # Example
+/// Output format for summaries.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Colorized text for the terminal
+    #[default]
+    Text,
+    /// A single JSON object
+    Json,
+    /// One CSV row per eligible day
+    Csv,
+    /// A markdown document
+    Markdown,
+}
+
+/// Summary data for a period, independent of how it is displayed.
 ///
-/// ```rust
-/// use dailylog::summary::parse_weekday;
-/// use chrono::Weekday;
+/// This is also the stable schema used for JSON output.
+#[derive(Serialize)]
+pub struct Summary {
+    /// First day of the summarized period (`YYYY-MM-DD`)
+    pub from: String,
+    /// Last day of the summarized period (`YYYY-MM-DD`)
+    pub to: String,
+    /// Tag the summary is restricted to, if any
+    pub tag: Option<String>,
+    /// Number of days in the period that fall on configured summary days
+    pub eligible_days: usize,
+    /// Number of eligible days with at least one entry
+    pub days_with_entries: usize,
+    /// Percentage of eligible days with entries
+    pub consistency: f64,
+    /// Total number of entries on eligible days
+    pub total_entries: usize,
+    /// Average number of entries per day with entries
+    pub entries_per_day: f64,
+    /// Total number of words across all entries
+    pub total_words: usize,
+    /// Average number of words per entry
+    pub average_entry_words: f64,
+    /// Consecutive eligible days with entries, up to today
+    pub current_streak: usize,
+    /// Longest run of consecutive eligible days with entries
+    pub longest_streak: usize,
+    /// Weekday with the most entries, if there are any entries
+    pub busiest_weekday: Option<String>,
+    /// Number of timestamped entries in each part of the day
+    pub time_of_day: TimeOfDay,
+    /// Entry counts per tag, most used first
+    pub tags: Vec<TagCount>,
+    /// Every eligible day, most recent first
+    pub days: Vec<DaySummary>,
+}
+
+/// Number of entries carrying a tag.
+#[derive(Serialize)]
+pub struct TagCount {
+    /// The tag name, without the leading `#`
+    pub tag: String,
+    /// Number of entries carrying the tag
+    pub count: usize,
+}
+
+/// Number of entries written in each part of the day.
 ///
-/// assert_eq!(parse_weekday("monday"), Some(Weekday::Mon));
-/// assert_eq!(parse_weekday("tue"), Some(Weekday::Tue));
-/// assert_eq!(parse_weekday("invalid"), None);
-/// ```
-fn parse_weekday(day_str: &str) -> Option<Weekday> {
-    match day_str.to_lowercase().as_str() {
-        "monday" | "mon" => Some(Weekday::Mon),
-        "tuesday" | "tue" => Some(Weekday::Tue),
-        "wednesday" | "wed" => Some(Weekday::Wed),
-        "thursday" | "thu" => Some(Weekday::Thu),
-        "friday" | "fri" => Some(Weekday::Fri),
-        "saturday" | "sat" => Some(Weekday::Sat),
-        "sunday" | "sun" => Some(Weekday::Sun),
-        _ => None,
+/// Derived from the `HH:MM` timestamps in entry headers; untitled entries
+/// have no timestamp and are not counted.
+#[derive(Default, Serialize)]
+pub struct TimeOfDay {
+    /// Entries written from 05:00 to 11:59
+    pub morning: usize,
+    /// Entries written from 12:00 to 16:59
+    pub afternoon: usize,
+    /// Entries written from 17:00 to 21:59
+    pub evening: usize,
+    /// Entries written from 22:00 to 04:59
+    pub night: usize,
+}
+
+impl TimeOfDay {
+    /// Counts an entry written at the given hour.
+    fn record(&mut self, hour: u32) {
+        match hour {
+            5..=11 => self.morning += 1,
+            12..=16 => self.afternoon += 1,
+            17..=21 => self.evening += 1,
+            _ => self.night += 1,
+        }
+    }
+
+    /// Returns the buckets as `(label, count)` pairs in display order.
+    fn buckets(&self) -> [(&'static str, usize); 4] {
+        [
+            ("Morning (05-12)", self.morning),
+            ("Afternoon (12-17)", self.afternoon),
+            ("Evening (17-22)", self.evening),
+            ("Night (22-05)", self.night),
+        ]
     }
 }
 
+/// Summary of a single eligible day.
+#[derive(Serialize)]
+pub struct DaySummary {
+    /// The day (`YYYY-MM-DD`)
+    pub date: String,
+    /// Full weekday name, e.g. `Monday`
+    pub weekday: String,
+    /// Number of entries logged that day
+    pub entry_count: usize,
+    /// Number of words written that day
+    pub word_count: usize,
+    /// Titles of the day's entries
+    pub titles: Vec<String>,
+    /// First line of the day's log, shown in text output when there are no titles
+    #[serde(skip)]
+    pub preview: Option<String>,
+}
+
 /// Generates and displays a summary of log entries over a specified period.
 ///
-/// Analyzes log files for the past N days and provides:
+/// Analyzes log files over a date range (such as the past N days, a calendar
+/// week or a month) and provides:
 /// - Summary statistics (total entries, consistency percentage)
+/// - Streaks, word counts, busiest weekday and time-of-day distribution
+/// - Per-tag breakdown of the `#hashtags` used in entries
 /// - Daily breakdown showing entry titles for each day
-/// - Colorized output for easy reading
+/// - Colorized output for easy reading, or JSON, CSV and markdown output
 /// - Filtering based on configured summary days (e.g., weekdays only)
 ///
+/// When a tag is given, only entries carrying that tag are counted and shown.
+///
 /// # Arguments
 ///
+/// * `out` - Where to write the summary
 /// * `log_dir` - The directory containing log files
-/// * `days` - Number of days to analyze (going backwards from today)
+/// * `range` - The days to analyze; days after today are ignored
 /// * `config` - Application configuration containing summary day filters
+/// * `tag` - Optional tag to restrict the summary to
+/// * `format` - How to print the summary
+/// * `theme` - The styles to use for text output
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// - Log files cannot be read
-/// - Terminal output fails
+/// - Writing the output fails
 ///
 /// # Example
 ///
 /// ```rust
-/// use dailylog::summary::summarize_logs;
+/// use dailylog::summary::{summarize_logs, OutputFormat};
 /// use dailylog::config::load_config;
+/// use dailylog::date::{today, DateRange};
+/// use dailylog::theme::Theme;
+/// use termcolor::{ColorChoice, StandardStream};
 ///
 /// let config = load_config()?;
-/// summarize_logs("/path/to/logs", 7, &config)?;
+/// let range = DateRange::last_days(7, today())?;
+/// let mut out = StandardStream::stdout(ColorChoice::Auto);
+/// summarize_logs(
+///     &mut out,
+///     "/path/to/logs",
+///     &range,
+///     &config,
+///     Some("oncall"),
+///     OutputFormat::Json,
+///     &Theme::default(),
+/// )?;
 /// ```
-pub fn summarize_logs(log_dir: &str, days: u32, config: &Config) -> anyhow::Result<()> {
-    let today = Local::now().date_naive();
-    let mut total_entries = 0;
-    let mut entries_by_day = Vec::new();
-    let mut total_eligible_days = 0;
+pub fn summarize_logs(
+    out: &mut impl WriteColor,
+    log_dir: &str,
+    range: &DateRange,
+    config: &Config,
+    tag: Option<&str>,
+    format: OutputFormat,
+    theme: &Theme,
+) -> anyhow::Result<()> {
+    let summary = collect_summary(log_dir, range, config, tag)?;
+
+    match format {
+        OutputFormat::Text => print_summary_text(out, &summary, &range.label, theme),
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&summary)?)?;
+            Ok(())
+        }
+        OutputFormat::Csv => {
+            write!(out, "{}", summary_to_csv(&summary))?;
+            Ok(())
+        }
+        OutputFormat::Markdown => {
+            write!(out, "{}", summary_to_markdown(&summary))?;
+            Ok(())
+        }
+    }
+}
+
+/// Collects summary data for a date range.
+///
+/// Only days up to and including today are considered, so a range covering
+/// the current month does not count future days as missed.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory containing log files
+/// * `range` - The days to analyze
+/// * `config` - Application configuration containing summary day filters
+/// * `tag` - Optional tag to restrict the summary to
+///
+/// # Errors
+///
+/// Returns an error if log files cannot be read.
+pub fn collect_summary(
+    log_dir: &str,
+    range: &DateRange,
+    config: &Config,
+    tag: Option<&str>,
+) -> anyhow::Result<Summary> {
+    let today = today();
+    let mut days_with_entries = 0;
+    let mut daily = Vec::new();
+    let mut tag_counts: HashMap<String, usize> = HashMap::new();
+    let mut weekday_counts: HashMap<Weekday, usize> = HashMap::new();
+    let mut time_of_day = TimeOfDay::default();
 
     // Parse configured days into weekdays
     let allowed_weekdays: Vec<Weekday> = config
@@ -87,102 +249,385 @@ pub fn summarize_logs(log_dir: &str, days: u32, config: &Config) -> anyhow::Resu
         .filter_map(|day| parse_weekday(day))
         .collect();
 
-    // Print header
-    let mut stdout = StandardStream::stdout(ColorChoice::Auto);
-    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Cyan)).set_bold(true))?;
-    writeln!(stdout, "=== Log Summary for Past {} Days ===", days)?;
-    stdout.reset()?;
-
-    // Collect entries for each day
-    for i in 0..days {
-        let date = today - Duration::days(i as i64);
+    // Collect entries for each day, most recent first
+    for date in range.days_until(today) {
         let weekday = date.weekday();
 
         // Check if this day is in our allowed days
         if allowed_weekdays.contains(&weekday) {
-            total_eligible_days += 1;
-            let log_path = get_log_file_path_for_date(log_dir, date);
-
-            if log_path.exists() {
-                let content = fs::read_to_string(&log_path)?;
-                if !content.trim().is_empty() {
-                    total_entries += 1;
-                    entries_by_day.push((date, content));
+            let mut day = DayLog::load(log_dir, date)?;
+            day.entries
+                .retain(|entry| tag.is_none_or(|tag| entry.has_tag(tag)));
+
+            if !day.is_empty() {
+                days_with_entries += 1;
+                *weekday_counts.entry(weekday).or_insert(0) += day.entries.len();
+                for entry in &day.entries {
+                    for entry_tag in &entry.tags {
+                        *tag_counts.entry(entry_tag.clone()).or_insert(0) += 1;
+                    }
+                    if let Some(time) = entry.time {
+                        time_of_day.record(time.hour());
+                    }
                 }
             }
+
+            daily.push(summarize_day(&day));
+        }
+    }
+
+    let mut tags: Vec<TagCount> = tag_counts
+        .into_iter()
+        .map(|(tag, count)| TagCount { tag, count })
+        .collect();
+    tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+
+    let eligible_days = daily.len();
+    let consistency = ratio(days_with_entries, eligible_days) * 100.0;
+    let total_entries: usize = daily.iter().map(|day| day.entry_count).sum();
+    let total_words: usize = daily.iter().map(|day| day.word_count).sum();
+    let (current_streak, longest_streak) =
+        compute_streaks(log_dir, today, &allowed_weekdays, tag)?;
+
+    // Ties go to the earliest day of the week
+    let busiest_weekday = allowed_weekdays
+        .iter()
+        .filter_map(|weekday| weekday_counts.get(weekday).map(|count| (*weekday, *count)))
+        .max_by(|a, b| {
+            a.1.cmp(&b.1)
+                .then_with(|| b.0.num_days_from_monday().cmp(&a.0.num_days_from_monday()))
+        })
+        .map(|(weekday, _)| weekday_name(weekday).to_string());
+
+    Ok(Summary {
+        from: format_date(range.start),
+        to: format_date(range.end),
+        tag: tag.map(|tag| tag.trim_start_matches('#').to_string()),
+        eligible_days,
+        days_with_entries,
+        consistency,
+        total_entries,
+        entries_per_day: ratio(total_entries, days_with_entries),
+        total_words,
+        average_entry_words: ratio(total_words, total_entries),
+        current_streak,
+        longest_streak,
+        busiest_weekday,
+        time_of_day,
+        tags,
+        days: daily,
+    })
+}
+
+/// Computes the current and longest logging streaks across all logs.
+///
+/// A streak is a run of consecutive days with entries, counting only days in
+/// `allowed_weekdays`, so a weekday-only configuration does not break the
+/// streak over weekends. Today does not break the current streak until it is
+/// over, since there is still time to write an entry.
+///
+/// # Errors
+///
+/// Returns an error if log files cannot be read.
+fn compute_streaks(
+    log_dir: &str,
+    today: NaiveDate,
+    allowed_weekdays: &[Weekday],
+    tag: Option<&str>,
+) -> anyhow::Result<(usize, usize)> {
+    let mut logged_dates = HashSet::new();
+    for date in list_log_dates(log_dir)? {
+        let day = DayLog::load(log_dir, date)?;
+        if day.entries.iter().any(|entry| tag.is_none_or(|tag| entry.has_tag(tag))) {
+            logged_dates.insert(date);
         }
     }
 
-    if entries_by_day.is_empty() {
-        println!(
-            "No log entries found for the past {} days on configured days.",
-            days
-        );
+    let Some(first) = logged_dates.iter().min().copied() else {
+        return Ok((0, 0));
+    };
+
+    let mut current = 0;
+    let mut longest = 0;
+    for date in first.iter_days().take_while(|date| *date <= today) {
+        if !allowed_weekdays.contains(&date.weekday()) {
+            continue;
+        }
+        if logged_dates.contains(&date) {
+            current += 1;
+            longest = longest.max(current);
+        } else if date != today {
+            current = 0;
+        }
+    }
+
+    Ok((current, longest))
+}
+
+/// Divides two counts, returning 0 when the denominator is 0.
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+/// Returns the full English name of a weekday.
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+/// Builds the summary of a single day from its (already filtered) entries.
+fn summarize_day(day: &DayLog) -> DaySummary {
+    DaySummary {
+        date: format_date(day.date),
+        weekday: day.date.format("%A").to_string(),
+        entry_count: day.entries.len(),
+        word_count: day.entries.iter().map(Entry::word_count).sum(),
+        titles: extract_entry_titles(&day.entries),
+        preview: day
+            .entries
+            .first()
+            .and_then(|entry| entry.body.lines().next())
+            .map(|line| line.trim().to_string()),
+    }
+}
+
+/// Formats a date as `YYYY-MM-DD`.
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// Prints a summary as colorized text.
+fn print_summary_text(
+    out: &mut impl WriteColor,
+    summary: &Summary,
+    label: &str,
+    theme: &Theme,
+) -> anyhow::Result<()> {
+    // Print header
+    out.set_color(&theme.header)?;
+    match &summary.tag {
+        Some(tag) => writeln!(out, "=== Log Summary for {} (#{}) ===", label, tag)?,
+        None => writeln!(out, "=== Log Summary for {} ===", label)?,
+    }
+    out.reset()?;
+
+    if summary.days_with_entries == 0 {
+        writeln!(
+            out,
+            "No log entries found between {} and {} on configured days.",
+            summary.from, summary.to
+        )?;
         return Ok(());
     }
 
     // Print summary statistics
-    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)).set_bold(true))?;
-    writeln!(stdout, "\nSummary Statistics:")?;
-    stdout.reset()?;
-    println!("- Total days with entries: {}", total_entries);
-    println!(
+    out.set_color(&theme.stat_label)?;
+    writeln!(out, "\nSummary Statistics:")?;
+    out.reset()?;
+    writeln!(out, "- Total days with entries: {}", summary.days_with_entries)?;
+    writeln!(
+        out,
         "- Logging consistency: {:.1}% ({}/{} days)",
-        (total_entries as f64 / total_eligible_days as f64) * 100.0,
-        total_entries,
-        total_eligible_days
-    );
+        summary.consistency, summary.days_with_entries, summary.eligible_days
+    )?;
+    writeln!(
+        out,
+        "- Total entries: {} ({:.1} per day with entries)",
+        summary.total_entries, summary.entries_per_day
+    )?;
+    writeln!(
+        out,
+        "- Words written: {} ({:.0} per entry on average)",
+        summary.total_words, summary.average_entry_words
+    )?;
+    writeln!(
+        out,
+        "- Current streak: {} {} (longest: {})",
+        summary.current_streak,
+        if summary.current_streak == 1 { "day" } else { "days" },
+        summary.longest_streak
+    )?;
+    if let Some(weekday) = &summary.busiest_weekday {
+        writeln!(out, "- Busiest weekday: {}", weekday)?;
+    }
+
+    // Print time-of-day distribution
+    let timestamped = summary.time_of_day.buckets().iter().map(|b| b.1).sum::<usize>();
+    if timestamped > 0 {
+        out.set_color(&theme.stat_label)?;
+        writeln!(out, "\nTime of Day:")?;
+        out.reset()?;
+        for (label, count) in summary.time_of_day.buckets() {
+            let bar_width = (ratio(count, timestamped) * 20.0).round() as usize;
+            write!(out, "- {:<18} {:>3}", label, count)?;
+            if bar_width > 0 {
+                out.set_color(&theme.chart)?;
+                write!(out, " {}", "█".repeat(bar_width))?;
+                out.reset()?;
+            }
+            writeln!(out)?;
+        }
+    }
+
+    // Print per-tag breakdown
+    if !summary.tags.is_empty() {
+        out.set_color(&theme.stat_label)?;
+        writeln!(out, "\nTags:")?;
+        out.reset()?;
+        for tag_count in &summary.tags {
+            writeln!(
+                out,
+                "- #{}: {} {}",
+                tag_count.tag,
+                tag_count.count,
+                if tag_count.count == 1 { "entry" } else { "entries" }
+            )?;
+        }
+    }
 
     // Show entries by day (most recent first)
-    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)).set_bold(true))?;
-    writeln!(stdout, "\nDaily Entries:")?;
-    stdout.reset()?;
+    out.set_color(&theme.stat_label)?;
+    writeln!(out, "\nDaily Entries:")?;
+    out.reset()?;
 
-    for (date, content) in entries_by_day {
+    for day in summary.days.iter().filter(|day| day.entry_count > 0) {
         // Print date header
-        stdout.set_color(ColorSpec::new().set_fg(Some(Color::Magenta)).set_bold(true))?;
-        writeln!(stdout, "\n--- {} ---", date.format("%Y-%m-%d (%A)"),)?;
-        stdout.reset()?;
-
-        // Extract and show titles/headers from the content
-        let titles = extract_entry_titles(&content);
-        if !titles.is_empty() {
-            stdout.set_color(ColorSpec::new().set_fg(Some(Color::Blue)))?;
-            for title in titles {
-                println!("  - {}", title);
+        out.set_color(&theme.date_header)?;
+        writeln!(out, "\n--- {} ({}) ---", day.date, day.weekday)?;
+        out.reset()?;
+
+        if !day.titles.is_empty() {
+            out.set_color(&theme.entry_title)?;
+            for title in &day.titles {
+                writeln!(out, "  - {}", title)?;
             }
-            stdout.reset()?;
+            out.reset()?;
         } else {
-            // If no clear titles, show first line or two
-            let lines: Vec<&str> = content.lines().take(2).collect();
-            stdout.set_color(ColorSpec::new().set_fg(Some(Color::White)))?;
-            for line in lines {
-                if !line.trim().is_empty() {
-                    println!("  {}", line.trim());
-                    break;
-                }
+            // If no clear titles, show the first line of the first entry
+            out.set_color(&theme.muted)?;
+            if let Some(preview) = &day.preview {
+                writeln!(out, "  {}", preview)?;
             }
-            stdout.reset()?;
+            out.reset()?;
         }
     }
 
     // Print footer
-    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Cyan)).set_bold(true))?;
-    writeln!(stdout, "\n=== End of Summary ===")?;
-    stdout.reset()?;
+    out.set_color(&theme.header)?;
+    writeln!(out, "\n=== End of Summary ===")?;
+    out.reset()?;
 
     Ok(())
 }
 
-/// Extracts entry titles from log file content.
+/// Renders a summary as CSV with one row per eligible day.
 ///
-/// Parses markdown content to find entry titles from:
-/// - Level 2 headers in timestamp format: `## HH:MM - title`
-/// - Other markdown headers (H1, H3)
+/// Columns are `date,weekday,entry_count,titles,word_count`, where `titles` joins the
+/// day's entry titles with `"; "`. Days without entries have an
+/// `entry_count` of 0, so the consistency statistics can be derived from
+/// the rows.
+fn summary_to_csv(summary: &Summary) -> String {
+    let mut csv = String::from("date,weekday,entry_count,titles,word_count\n");
+    for day in &summary.days {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            day.date,
+            day.weekday,
+            day.entry_count,
+            csv_field(&day.titles.join("; ")),
+            day.word_count
+        ));
+    }
+    csv
+}
+
+/// Quotes a CSV field if it contains a delimiter, quote or newline.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Renders a summary as a markdown document.
+fn summary_to_markdown(summary: &Summary) -> String {
+    let mut md = match &summary.tag {
+        Some(tag) => format!("# Log Summary: {} to {} (#{})\n\n", summary.from, summary.to, tag),
+        None => format!("# Log Summary: {} to {}\n\n", summary.from, summary.to),
+    };
+
+    md.push_str(&format!("- Days with entries: {}\n", summary.days_with_entries));
+    md.push_str(&format!(
+        "- Logging consistency: {:.1}% ({}/{} days)\n",
+        summary.consistency, summary.days_with_entries, summary.eligible_days
+    ));
+    md.push_str(&format!(
+        "- Total entries: {} ({:.1} per day with entries)\n",
+        summary.total_entries, summary.entries_per_day
+    ));
+    md.push_str(&format!(
+        "- Words written: {} ({:.0} per entry on average)\n",
+        summary.total_words, summary.average_entry_words
+    ));
+    md.push_str(&format!(
+        "- Current streak: {} (longest: {})\n",
+        summary.current_streak, summary.longest_streak
+    ));
+    if let Some(weekday) = &summary.busiest_weekday {
+        md.push_str(&format!("- Busiest weekday: {}\n", weekday));
+    }
+
+    md.push_str("\n## Time of Day\n\n");
+    for (label, count) in summary.time_of_day.buckets() {
+        md.push_str(&format!("- {}: {}\n", label, count));
+    }
+
+    if !summary.tags.is_empty() {
+        md.push_str("\n## Tags\n\n");
+        for tag_count in &summary.tags {
+            md.push_str(&format!("- #{}: {}\n", tag_count.tag, tag_count.count));
+        }
+    }
+
+    md.push_str("\n## Daily Entries\n");
+    for day in summary.days.iter().filter(|day| day.entry_count > 0) {
+        md.push_str(&format!("\n### {} ({})\n\n", day.date, day.weekday));
+        if day.titles.is_empty() {
+            if let Some(preview) = &day.preview {
+                md.push_str(&format!("{}\n", preview));
+            }
+        } else {
+            for title in &day.titles {
+                md.push_str(&format!("- {}\n", title));
+            }
+        }
+    }
+
+    md
+}
+
+/// Extracts entry titles from a day's entries.
+///
+/// Collects titles from:
+/// - Entry headers in timestamp format: `## HH:MM - title` or
+///   `## HH:MM +02:00 - title`
+/// - Other markdown headers (H1, H3) within entry bodies
 ///
 /// # Arguments
 ///
-/// * `content` - The markdown content to parse
+/// * `entries` - The entries to extract titles from
 ///
 /// # Returns
 ///
@@ -191,28 +636,30 @@ pub fn summarize_logs(log_dir: &str, days: u32, config: &Config) -> anyhow::Resu
 /// # Example
 ///
 /// ```rust
+/// use chrono::NaiveDate;
+/// use dailylog::daylog::DayLog;
 /// use dailylog::summary::extract_entry_titles;
 ///
 /// let content = "## 14:30 - Meeting notes\n\nDiscussed project timeline.\n\n## 16:00 - Code review";
-/// let titles = extract_entry_titles(content);
+/// let day = DayLog::parse(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(), content);
+/// let titles = extract_entry_titles(&day.entries);
 /// assert_eq!(titles, vec!["Meeting notes", "Code review"]);
 /// ```
-fn extract_entry_titles(content: &str) -> Vec<String> {
+pub fn extract_entry_titles(entries: &[Entry]) -> Vec<String> {
     let mut titles = Vec::new();
 
-    for line in content.lines() {
-        let trimmed = line.trim();
-        // Look for markdown headers (## timestamp - title format)
-        if trimmed.starts_with("## ") && trimmed.contains(" - ") {
-            if let Some(title_part) = trimmed.split(" - ").nth(1) {
-                titles.push(title_part.to_string());
-            }
+    for entry in entries {
+        if let Some(title) = &entry.title {
+            titles.push(title.clone());
         }
         // Also look for other markdown headers
-        else if trimmed.starts_with("# ") || trimmed.starts_with("### ") {
-            titles.push(trimmed.trim_start_matches('#').trim().to_string());
+        for line in entry.body.lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with("# ") || trimmed.starts_with("### ") {
+                titles.push(trimmed.trim_start_matches('#').trim().to_string());
+            }
         }
     }
 
     titles
-}
\ No newline at end of file
+}
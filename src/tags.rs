@@ -0,0 +1,108 @@
+//! Hashtag listing and statistics.
+//!
+//! This module collects the `#hashtags` used in log entries and displays
+//! how often each one has been used and when it was last seen.
+
+use crate::{daylog::DayLog, entry::list_log_dates, theme::Theme};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use termcolor::WriteColor;
+
+/// Usage statistics for a single tag.
+pub struct TagStats {
+    /// The tag name, lowercased and without the leading `#`
+    pub tag: String,
+    /// Number of entries carrying the tag
+    pub count: usize,
+    /// The most recent date the tag was used
+    pub last_used: NaiveDate,
+}
+
+/// Collects usage statistics for every tag across all logs.
+///
+/// Tags are counted once per entry. The result is sorted by descending
+/// count, then alphabetically.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory containing log files
+///
+/// # Errors
+///
+/// Returns an error if the log directory or a log file cannot be read.
+pub fn collect_tag_stats(log_dir: &str) -> anyhow::Result<Vec<TagStats>> {
+    let mut stats: HashMap<String, TagStats> = HashMap::new();
+
+    for date in list_log_dates(log_dir)? {
+        let day = DayLog::load(log_dir, date)?;
+        for entry in &day.entries {
+            for tag in &entry.tags {
+                let stat = stats.entry(tag.clone()).or_insert(TagStats {
+                    tag: tag.clone(),
+                    count: 0,
+                    last_used: date,
+                });
+                stat.count += 1;
+                stat.last_used = stat.last_used.max(date);
+            }
+        }
+    }
+
+    let mut stats: Vec<TagStats> = stats.into_values().collect();
+    stats.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+    Ok(stats)
+}
+
+/// Displays every tag with its entry count and last-used date.
+///
+/// # Arguments
+///
+/// * `out` - Where to write the table
+/// * `log_dir` - The directory containing log files
+/// * `theme` - The styles to use
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - Log files cannot be read
+/// - Terminal output fails
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::tags::list_tags;
+/// use dailylog::theme::Theme;
+/// use termcolor::{ColorChoice, StandardStream};
+///
+/// let mut out = StandardStream::stdout(ColorChoice::Auto);
+/// list_tags(&mut out, "/path/to/logs", &Theme::default())?;
+/// ```
+pub fn list_tags(out: &mut impl WriteColor, log_dir: &str, theme: &Theme) -> anyhow::Result<()> {
+    let stats = collect_tag_stats(log_dir)?;
+
+    if stats.is_empty() {
+        writeln!(out, "No tags found. Add #hashtags to your entries to tag them.")?;
+        return Ok(());
+    }
+
+    let width = stats.iter().map(|stat| stat.tag.len()).max().unwrap_or(0) + 1;
+
+    out.set_color(&theme.header)?;
+    writeln!(out, "=== Tags ===")?;
+    out.reset()?;
+
+    for stat in stats {
+        out.set_color(&theme.bullet)?;
+        write!(out, "{:<width$}", format!("#{}", stat.tag), width = width)?;
+        out.reset()?;
+        writeln!(
+            out,
+            "  {:>4} {}  last used {}",
+            stat.count,
+            if stat.count == 1 { "entry  " } else { "entries" },
+            stat.last_used.format("%Y-%m-%d")
+        )?;
+    }
+
+    Ok(())
+}
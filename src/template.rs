@@ -0,0 +1,179 @@
+//! Templates for new entries.
+//!
+//! Templates are markdown files in the `templates` folder of the log
+//! directory (`~/.dailylog/templates/standup.md` by default). A template is
+//! expanded and loaded into the editor when writing a new entry, chosen with
+//! `-t NAME` or the `default_template` config option.
+
+use crate::{
+    config::Config,
+    date,
+    daylog::DayLog,
+    report::previous_working_day,
+    summary::extract_entry_titles,
+};
+use chrono::NaiveDate;
+use regex::Regex;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+/// Matches `{{placeholder}}` names, allowing spaces inside the braces.
+static PLACEHOLDER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*(\w+)\s*\}\}").expect("valid placeholder pattern"));
+
+/// Returns the directory that holds entry templates.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory where log files are stored
+pub fn templates_dir(log_dir: &str) -> PathBuf {
+    Path::new(log_dir).join("templates")
+}
+
+/// Lists the names of all templates, sorted alphabetically.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory where log files are stored
+///
+/// # Errors
+///
+/// Returns an error if the templates directory exists but cannot be read.
+pub fn list_templates(log_dir: &str) -> anyhow::Result<Vec<String>> {
+    let dir = templates_dir(log_dir);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut names = Vec::new();
+    for dir_entry in fs::read_dir(dir)? {
+        let path = dir_entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+            continue;
+        }
+        if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+            names.push(name.to_string());
+        }
+    }
+
+    names.sort();
+    Ok(names)
+}
+
+/// Reads the raw content of a named template.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory where log files are stored
+/// * `name` - The template name, without the `.md` extension
+///
+/// # Errors
+///
+/// Returns an error, listing the available templates, if the template does
+/// not exist or cannot be read.
+pub fn load_template(log_dir: &str, name: &str) -> anyhow::Result<String> {
+    let path = templates_dir(log_dir).join(format!("{}.md", name.trim_end_matches(".md")));
+    if !path.is_file() {
+        let available = list_templates(log_dir)?;
+        let hint = if available.is_empty() {
+            format!("create one at {:?}", path)
+        } else {
+            format!("available templates: {}", available.join(", "))
+        };
+        return Err(anyhow::anyhow!("Template '{}' not found ({})", name, hint));
+    }
+
+    fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to read template {:?}: {}", path, e))
+}
+
+/// Expands the placeholders in a template for an entry on `date`.
+///
+/// Supported placeholders:
+/// - `{{date}}`: the entry's date as `YYYY-MM-DD`
+/// - `{{time}}`: the current time as `HH:MM`
+/// - `{{weekday}}`: the entry's weekday, such as `Monday`
+/// - `{{yesterday_titles}}`: a markdown list of the entry titles from the
+///   previous day in `summary_days` (Friday, on a Monday)
+///
+/// Unknown placeholders are left untouched.
+///
+/// # Arguments
+///
+/// * `template` - The raw template content
+/// * `date` - The day the entry is being written for
+/// * `config` - Application configuration (log directory and summary days)
+///
+/// # Errors
+///
+/// Returns an error if the previous day's log cannot be read.
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::config::load_config;
+/// use dailylog::date::today;
+/// use dailylog::template::expand_template;
+///
+/// let config = load_config()?;
+/// let text = expand_template("## {{weekday}} {{date}}", today(), &config)?;
+/// ```
+pub fn expand_template(template: &str, date: NaiveDate, config: &Config) -> anyhow::Result<String> {
+    let yesterday_titles = if template.contains("yesterday_titles") {
+        let previous = previous_working_day(date, config);
+        let titles = extract_entry_titles(&DayLog::load(&config.log_dir, previous)?.entries);
+        if titles.is_empty() {
+            "- Nothing logged".to_string()
+        } else {
+            titles
+                .iter()
+                .map(|title| format!("- {}", title))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    } else {
+        String::new()
+    };
+
+    let expanded = PLACEHOLDER.replace_all(template, |captures: &regex::Captures| {
+        match &captures[1] {
+            "date" => date.format("%Y-%m-%d").to_string(),
+            "time" => date::now().format("%H:%M").to_string(),
+            "weekday" => date.format("%A").to_string(),
+            "yesterday_titles" => yesterday_titles.clone(),
+            _ => captures[0].to_string(),
+        }
+    });
+
+    Ok(expanded.into_owned())
+}
+
+/// Returns the expanded template for a new entry, if one applies.
+///
+/// Uses the template given by name (from `-t`), falling back to the
+/// `default_template` config option. Returns `None` when neither is set.
+///
+/// # Arguments
+///
+/// * `config` - Application configuration
+/// * `name` - The template requested on the command line, if any
+/// * `date` - The day the entry is being written for
+///
+/// # Errors
+///
+/// Returns an error if the template does not exist or cannot be expanded.
+pub fn entry_template(
+    config: &Config,
+    name: Option<&str>,
+    date: NaiveDate,
+) -> anyhow::Result<Option<String>> {
+    let Some(name) = name.or(config.default_template.as_deref()) else {
+        return Ok(None);
+    };
+
+    let template = load_template(&config.log_dir, name)?;
+    expand_template(&template, date, config).map(Some)
+}
@@ -0,0 +1,333 @@
+//! Color themes and color output control.
+//!
+//! Output is styled by semantic role (date headers, entry titles, bullets,
+//! code, statistic labels and so on) rather than fixed colors. A `[theme]`
+//! section in the config picks a built-in theme and can override any role.
+
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::{
+    env,
+    io::{self, IsTerminal},
+    str::FromStr,
+};
+use termcolor::{Color, ColorChoice, ColorSpec};
+
+/// When to use colors, as given by `--color`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorMode {
+    /// Use colors when stdout is a terminal and `NO_COLOR` is not set
+    #[default]
+    Auto,
+    /// Always use colors, even when piped or `NO_COLOR` is set
+    Always,
+    /// Never use colors
+    Never,
+}
+
+impl ColorMode {
+    /// Resolves the mode into a `ColorChoice` for the current process.
+    pub fn choice(self) -> ColorChoice {
+        match self {
+            ColorMode::Auto => {
+                let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+                if io::stdout().is_terminal() && !no_color {
+                    ColorChoice::Auto
+                } else {
+                    ColorChoice::Never
+                }
+            }
+            ColorMode::Always => ColorChoice::Always,
+            ColorMode::Never => ColorChoice::Never,
+        }
+    }
+}
+
+/// A style for one role, as written in the config file.
+///
+/// Colors accept the names `black`, `blue`, `green`, `red`, `cyan`,
+/// `magenta`, `yellow` and `white`, an ANSI 256-color number such as `"130"`,
+/// or an RGB triple such as `"255,128,0"`.
+#[derive(Deserialize, Default, Clone)]
+pub struct StyleConfig {
+    /// Foreground color
+    pub fg: Option<String>,
+    /// Background color
+    pub bg: Option<String>,
+    /// Bold text
+    #[serde(default)]
+    pub bold: bool,
+    /// Italic text
+    #[serde(default)]
+    pub italic: bool,
+    /// Underlined text
+    #[serde(default)]
+    pub underline: bool,
+    /// Dimmed text
+    #[serde(default)]
+    pub dimmed: bool,
+}
+
+/// The `[theme]` section of the config file.
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [theme]
+/// # Built-in theme to start from: "default", "light" or "mono"
+/// name = "light"
+///
+/// # Override individual roles
+/// entry_title = { fg = "blue", bold = true }
+/// code = { fg = "black", bg = "255" }
+/// ```
+#[derive(Deserialize, Default, Clone)]
+pub struct ThemeConfig {
+    /// Built-in theme to start from (default: "default")
+    pub name: Option<String>,
+    /// Style for section banners
+    pub header: Option<StyleConfig>,
+    /// Style for date lines
+    pub date_header: Option<StyleConfig>,
+    /// Style for entry titles
+    pub entry_title: Option<StyleConfig>,
+    /// Style for top-level markdown headings
+    pub heading: Option<StyleConfig>,
+    /// Style for deeper markdown headings
+    pub subheading: Option<StyleConfig>,
+    /// Style for list bullets and tag names
+    pub bullet: Option<StyleConfig>,
+    /// Style for code
+    pub code: Option<StyleConfig>,
+    /// Style for links
+    pub link: Option<StyleConfig>,
+    /// Style for secondary text
+    pub muted: Option<StyleConfig>,
+    /// Style for summary section labels
+    pub stat_label: Option<StyleConfig>,
+    /// Style for chart bars
+    pub chart: Option<StyleConfig>,
+    /// Style for search matches
+    pub highlight: Option<StyleConfig>,
+}
+
+/// Resolved styles for every semantic role.
+#[derive(Clone, Debug)]
+pub struct Theme {
+    /// Section banners such as `=== Log Summary ===` and `=== Tags ===`
+    pub header: ColorSpec,
+    /// Date lines framing a day's log, summary day or search hits
+    pub date_header: ColorSpec,
+    /// Entry headers (`## 14:30 - Title`) and entry titles in lists
+    pub entry_title: ColorSpec,
+    /// Top-level markdown headings inside entries
+    pub heading: ColorSpec,
+    /// Third-level and deeper markdown headings inside entries
+    pub subheading: ColorSpec,
+    /// List bullets and numbers, completed tasks and tag names
+    pub bullet: ColorSpec,
+    /// Inline code and code blocks
+    pub code: ColorSpec,
+    /// Link text
+    pub link: ColorSpec,
+    /// Secondary text: previews, link targets, quote bars and rules
+    pub muted: ColorSpec,
+    /// Section labels in summaries, such as `Summary Statistics:`
+    pub stat_label: ColorSpec,
+    /// Bars in summary charts
+    pub chart: ColorSpec,
+    /// Search matches
+    pub highlight: ColorSpec,
+}
+
+/// Names of the built-in themes.
+pub const BUILTIN_THEMES: [&str; 3] = ["default", "light", "mono"];
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            header: spec(Some(Color::Cyan), None, &[Bold]),
+            date_header: spec(Some(Color::Magenta), None, &[Bold]),
+            entry_title: spec(Some(Color::Cyan), None, &[Bold]),
+            heading: spec(Some(Color::Blue), None, &[Bold]),
+            subheading: spec(Some(Color::Green), None, &[Bold]),
+            bullet: spec(Some(Color::Yellow), None, &[]),
+            code: spec(Some(Color::White), Some(Color::Black), &[]),
+            link: spec(Some(Color::Blue), None, &[Underline]),
+            muted: spec(None, None, &[Dimmed]),
+            stat_label: spec(Some(Color::Green), None, &[Bold]),
+            chart: spec(Some(Color::Blue), None, &[]),
+            highlight: spec(Some(Color::Red), None, &[Bold]),
+        }
+    }
+}
+
+impl Theme {
+    /// Returns a built-in theme by name.
+    ///
+    /// - `default`: the classic colors, for dark terminal backgrounds
+    /// - `light`: darker 256-color shades that stay readable on light backgrounds
+    /// - `mono`: no colors, only bold, italic, underline and dim
+    pub fn builtin(name: &str) -> Option<Theme> {
+        match name.to_lowercase().as_str() {
+            "default" | "dark" => Some(Theme::default()),
+            "light" => Some(Theme {
+                header: spec(Some(Color::Ansi256(25)), None, &[Bold]),
+                date_header: spec(Some(Color::Ansi256(90)), None, &[Bold]),
+                entry_title: spec(Some(Color::Ansi256(25)), None, &[Bold]),
+                heading: spec(Some(Color::Ansi256(18)), None, &[Bold]),
+                subheading: spec(Some(Color::Ansi256(22)), None, &[Bold]),
+                bullet: spec(Some(Color::Ansi256(130)), None, &[]),
+                code: spec(Some(Color::Ansi256(52)), Some(Color::Ansi256(255)), &[]),
+                link: spec(Some(Color::Ansi256(25)), None, &[Underline]),
+                muted: spec(Some(Color::Ansi256(243)), None, &[]),
+                stat_label: spec(Some(Color::Ansi256(22)), None, &[Bold]),
+                chart: spec(Some(Color::Ansi256(25)), None, &[]),
+                highlight: spec(Some(Color::Ansi256(160)), None, &[Bold]),
+            }),
+            "mono" => Some(Theme {
+                header: spec(None, None, &[Bold]),
+                date_header: spec(None, None, &[Bold, Underline]),
+                entry_title: spec(None, None, &[Bold]),
+                heading: spec(None, None, &[Bold, Underline]),
+                subheading: spec(None, None, &[Bold]),
+                bullet: spec(None, None, &[Bold]),
+                code: spec(None, None, &[Italic]),
+                link: spec(None, None, &[Underline]),
+                muted: spec(None, None, &[Dimmed]),
+                stat_label: spec(None, None, &[Bold]),
+                chart: spec(None, None, &[]),
+                highlight: spec(None, None, &[Bold, Underline]),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Builds a theme from the `[theme]` config section.
+    ///
+    /// Starts from the named built-in theme (or `default`) and replaces the
+    /// style of every role that the config sets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the theme name or a color is not recognized.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use dailylog::config::load_config;
+    /// use dailylog::theme::Theme;
+    ///
+    /// let config = load_config()?;
+    /// let theme = Theme::from_config(&config.theme)?;
+    /// ```
+    pub fn from_config(config: &ThemeConfig) -> anyhow::Result<Theme> {
+        let name = config.name.as_deref().unwrap_or("default");
+        let mut theme = Theme::builtin(name).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown theme '{}': expected one of {}",
+                name,
+                BUILTIN_THEMES.join(", ")
+            )
+        })?;
+
+        let overrides = [
+            ("header", &config.header, &mut theme.header),
+            ("date_header", &config.date_header, &mut theme.date_header),
+            ("entry_title", &config.entry_title, &mut theme.entry_title),
+            ("heading", &config.heading, &mut theme.heading),
+            ("subheading", &config.subheading, &mut theme.subheading),
+            ("bullet", &config.bullet, &mut theme.bullet),
+            ("code", &config.code, &mut theme.code),
+            ("link", &config.link, &mut theme.link),
+            ("muted", &config.muted, &mut theme.muted),
+            ("stat_label", &config.stat_label, &mut theme.stat_label),
+            ("chart", &config.chart, &mut theme.chart),
+            ("highlight", &config.highlight, &mut theme.highlight),
+        ];
+        for (role, style, target) in overrides {
+            if let Some(style) = style {
+                *target = style_spec(role, style)?;
+            }
+        }
+
+        Ok(theme)
+    }
+}
+
+/// Returns `base` with the colors and attributes set in `layer` applied on top.
+///
+/// Used to nest styles, such as a search match inside an entry title or a
+/// link inside a heading.
+pub fn layered(base: &ColorSpec, layer: &ColorSpec) -> ColorSpec {
+    let mut spec = base.clone();
+    if let Some(fg) = layer.fg() {
+        spec.set_fg(Some(*fg));
+    }
+    if let Some(bg) = layer.bg() {
+        spec.set_bg(Some(*bg));
+    }
+    if layer.bold() {
+        spec.set_bold(true);
+    }
+    if layer.italic() {
+        spec.set_italic(true);
+    }
+    if layer.underline() {
+        spec.set_underline(true);
+    }
+    if layer.strikethrough() {
+        spec.set_strikethrough(true);
+    }
+    if layer.dimmed() {
+        spec.set_dimmed(true);
+    }
+    spec
+}
+
+/// Text attributes for building a `ColorSpec`.
+enum Attribute {
+    Bold,
+    Italic,
+    Underline,
+    Dimmed,
+}
+use Attribute::*;
+
+/// Builds a `ColorSpec` from colors and attributes.
+fn spec(fg: Option<Color>, bg: Option<Color>, attributes: &[Attribute]) -> ColorSpec {
+    let mut spec = ColorSpec::new();
+    spec.set_fg(fg).set_bg(bg);
+    for attribute in attributes {
+        match attribute {
+            Bold => spec.set_bold(true),
+            Italic => spec.set_italic(true),
+            Underline => spec.set_underline(true),
+            Dimmed => spec.set_dimmed(true),
+        };
+    }
+    spec
+}
+
+/// Converts a configured style into a `ColorSpec`.
+fn style_spec(role: &str, style: &StyleConfig) -> anyhow::Result<ColorSpec> {
+    let parse = |color: &Option<String>| -> anyhow::Result<Option<Color>> {
+        color
+            .as_deref()
+            .map(|color| {
+                Color::from_str(color.trim()).map_err(|e| {
+                    anyhow::anyhow!("Invalid color '{}' for theme.{}: {}", color, role, e)
+                })
+            })
+            .transpose()
+    };
+
+    let mut spec = ColorSpec::new();
+    spec.set_fg(parse(&style.fg)?)
+        .set_bg(parse(&style.bg)?)
+        .set_bold(style.bold)
+        .set_italic(style.italic)
+        .set_underline(style.underline)
+        .set_dimmed(style.dimmed);
+    Ok(spec)
+}
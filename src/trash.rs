@@ -0,0 +1,325 @@
+//! Trash for deleted and replaced day logs.
+//!
+//! Whenever a day's log is deleted or replaced (by `dailylog edit`, or by
+//! restoring a draft), its previous content is kept in the `.trash` folder of
+//! the log directory, stamped with the time it was trashed. Trashed logs are
+//! listed, restored and purged with `dailylog trash`. They are never synced,
+//! since git sync only picks up daily log files.
+
+use crate::{
+    daylog::DayLog,
+    entry::{get_log_file_path_for_date, read_log_file, write_day_log},
+    lock::lock_log_file,
+    theme::Theme,
+};
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use regex::Regex;
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+use termcolor::WriteColor;
+
+/// Matches trash file names: `{date}_{trashed}_{reason}.trash`.
+static TRASH_NAME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\d{4}-\d{2}-\d{2})_(\d{8}T\d{6})_(deleted|replaced)(?:_\d+)?\.trash$")
+        .expect("valid trash name pattern")
+});
+
+/// Format of the trash time in trash file names.
+const TRASHED_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// Why a day's log was moved to the trash.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrashReason {
+    /// The log was deleted
+    Deleted,
+    /// The log was overwritten with new content
+    Replaced,
+}
+
+impl TrashReason {
+    /// The name used for this reason in trash file names.
+    fn name(self) -> &'static str {
+        match self {
+            TrashReason::Deleted => "deleted",
+            TrashReason::Replaced => "replaced",
+        }
+    }
+}
+
+/// A trashed copy of a day's log.
+#[derive(Debug)]
+pub struct TrashItem {
+    /// Path of the trash file
+    pub path: PathBuf,
+    /// The day the log belongs to
+    pub date: NaiveDate,
+    /// When the log was moved to the trash
+    pub trashed: NaiveDateTime,
+    /// Whether the log was deleted or replaced
+    pub reason: TrashReason,
+}
+
+impl TrashItem {
+    /// Reads a trash item's details back from its file name.
+    fn from_path(path: PathBuf) -> Option<TrashItem> {
+        let name = path.file_name()?.to_str()?;
+        let captures = TRASH_NAME.captures(name)?;
+        let date = NaiveDate::parse_from_str(&captures[1], "%Y-%m-%d").ok()?;
+        let trashed = NaiveDateTime::parse_from_str(&captures[2], TRASHED_FORMAT).ok()?;
+        let reason = match &captures[3] {
+            "deleted" => TrashReason::Deleted,
+            _ => TrashReason::Replaced,
+        };
+        Some(TrashItem {
+            path,
+            date,
+            trashed,
+            reason,
+        })
+    }
+
+    /// Returns the trashed log's content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the trash file cannot be read.
+    pub fn read(&self) -> anyhow::Result<String> {
+        fs::read_to_string(&self.path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {:?}: {}", self.path, e))
+    }
+}
+
+/// Returns the directory that holds trashed logs.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory where log files are stored
+pub fn trash_dir(log_dir: &str) -> PathBuf {
+    Path::new(log_dir).join(".trash")
+}
+
+/// Saves the content of a day's log to the trash.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory where log files are stored
+/// * `date` - The day the log belongs to
+/// * `content` - The log's content
+/// * `reason` - Whether the log is being deleted or replaced
+///
+/// # Returns
+///
+/// The path of the new trash file.
+///
+/// # Errors
+///
+/// Returns an error if the trash directory or file cannot be written.
+pub fn move_to_trash(
+    log_dir: &str,
+    date: NaiveDate,
+    content: &str,
+    reason: TrashReason,
+) -> anyhow::Result<PathBuf> {
+    let dir = trash_dir(log_dir);
+    fs::create_dir_all(&dir)
+        .map_err(|e| anyhow::anyhow!("Failed to create trash directory {:?}: {}", dir, e))?;
+
+    let stem = format!(
+        "{}_{}_{}",
+        date.format("%Y-%m-%d"),
+        Local::now().format(TRASHED_FORMAT),
+        reason.name()
+    );
+
+    // create_new keeps two logs trashed in the same second apart
+    for attempt in 0.. {
+        let name = if attempt == 0 {
+            format!("{}.trash", stem)
+        } else {
+            format!("{}_{}.trash", stem, attempt)
+        };
+        let path = dir.join(name);
+
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                file.write_all(content.as_bytes())
+                    .and_then(|()| file.sync_all())
+                    .map_err(|e| anyhow::anyhow!("Failed to write {:?}: {}", path, e))?;
+                return Ok(path);
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => {
+                return Err(anyhow::anyhow!("Failed to create {:?}: {}", path, e));
+            }
+        }
+    }
+    unreachable!("attempts are unbounded")
+}
+
+/// Lists everything in the trash, most recently trashed first.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory where log files are stored
+///
+/// # Errors
+///
+/// Returns an error if the trash directory exists but cannot be read.
+pub fn list_trash(log_dir: &str) -> anyhow::Result<Vec<TrashItem>> {
+    let dir = trash_dir(log_dir);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut items = Vec::new();
+    for dir_entry in fs::read_dir(dir)? {
+        if let Some(item) = TrashItem::from_path(dir_entry?.path()) {
+            items.push(item);
+        }
+    }
+
+    items.sort_by(|a, b| b.trashed.cmp(&a.trashed).then_with(|| b.path.cmp(&a.path)));
+    Ok(items)
+}
+
+/// Returns trash item number `number` (starting at 1) as listed by `show_trash`.
+fn find_trash_item(log_dir: &str, number: usize) -> anyhow::Result<TrashItem> {
+    let items = list_trash(log_dir)?;
+    let count = items.len();
+    items
+        .into_iter()
+        .nth(number.wrapping_sub(1))
+        .ok_or_else(|| match count {
+            0 => anyhow::anyhow!("The trash is empty"),
+            _ => anyhow::anyhow!("No trash item {}: expected a number from 1 to {}", number, count),
+        })
+}
+
+/// Displays everything in the trash with a number, its day and its entries.
+///
+/// # Arguments
+///
+/// * `out` - Where to write the list
+/// * `log_dir` - The directory where log files are stored
+/// * `theme` - The styles to use
+///
+/// # Errors
+///
+/// Returns an error if the trash cannot be read or terminal output fails.
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::trash::show_trash;
+/// use dailylog::theme::Theme;
+/// use termcolor::{ColorChoice, StandardStream};
+///
+/// let mut out = StandardStream::stdout(ColorChoice::Auto);
+/// show_trash(&mut out, "/path/to/logs", &Theme::default())?;
+/// ```
+pub fn show_trash(out: &mut impl WriteColor, log_dir: &str, theme: &Theme) -> anyhow::Result<()> {
+    let items = list_trash(log_dir)?;
+    if items.is_empty() {
+        writeln!(out, "The trash is empty.")?;
+        return Ok(());
+    }
+
+    out.set_color(&theme.header)?;
+    writeln!(out, "=== Trash ===")?;
+    out.reset()?;
+
+    for (i, item) in items.iter().enumerate() {
+        let day = DayLog::parse(item.date, &item.read()?);
+        let count = day.entries.len();
+        let preview = day
+            .entries
+            .first()
+            .map(|entry| match &entry.title {
+                Some(title) => title.clone(),
+                None => entry.body.lines().next().unwrap_or_default().to_string(),
+            })
+            .map(|line| line.chars().take(40).collect::<String>())
+            .unwrap_or_default();
+
+        write!(out, "{:>3}. ", i + 1)?;
+        out.set_color(&theme.date_header)?;
+        write!(out, "{}", item.date.format("%Y-%m-%d"))?;
+        out.reset()?;
+        write!(
+            out,
+            "  {} {}  {}",
+            count,
+            if count == 1 { "entry" } else { "entries" },
+            preview
+        )?;
+        out.set_color(&theme.muted)?;
+        writeln!(out, "  ({} {})", item.reason.name(), item.trashed.format("%Y-%m-%d %H:%M"))?;
+        out.reset()?;
+    }
+
+    writeln!(out)?;
+    writeln!(out, "Restore one with `dailylog trash restore N` or delete it with `dailylog trash purge N`.")?;
+    Ok(())
+}
+
+/// Puts a trashed log back in place and removes it from the trash.
+///
+/// If the day has a log now, that log is moved to the trash first, so
+/// restoring never loses anything.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory where log files are stored
+/// * `number` - The item's number in the `dailylog trash` list
+///
+/// # Returns
+///
+/// The path of the restored log file.
+///
+/// # Errors
+///
+/// Returns an error if there is no item with that number, or the trash or
+/// log file cannot be read, locked or written.
+pub fn restore_from_trash(log_dir: &str, number: usize) -> anyhow::Result<PathBuf> {
+    let item = find_trash_item(log_dir, number)?;
+    let content = item.read()?;
+    let log_path = get_log_file_path_for_date(log_dir, item.date);
+
+    let _lock = lock_log_file(&log_path)?;
+    let current = read_log_file(&log_path)?;
+    write_day_log(log_dir, item.date, &current, &content)?;
+
+    fs::remove_file(&item.path)?;
+    Ok(log_path)
+}
+
+/// Deletes a trashed log for good, or everything in the trash when `number` is `None`.
+///
+/// # Arguments
+///
+/// * `log_dir` - The directory where log files are stored
+/// * `number` - The item's number in the `dailylog trash` list
+///
+/// # Returns
+///
+/// The number of items deleted.
+///
+/// # Errors
+///
+/// Returns an error if there is no item with that number or it cannot be deleted.
+pub fn purge_trash(log_dir: &str, number: Option<usize>) -> anyhow::Result<usize> {
+    let items = match number {
+        Some(number) => vec![find_trash_item(log_dir, number)?],
+        None => list_trash(log_dir)?,
+    };
+
+    let count = items.len();
+    for item in items {
+        fs::remove_file(&item.path)?;
+    }
+    Ok(count)
+}
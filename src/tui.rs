@@ -0,0 +1,564 @@
+//! Interactive full-screen journal browser.
+//!
+//! `dailylog tui` shows a month calendar and a list of logged days on the
+//! left and the selected day's rendered log on the right. Days can be browsed
+//! by day, week and month, searched, opened in the editor and synced with git
+//! without leaving the browser.
+
+use crate::{
+    config::Config,
+    date::today,
+    daylog::DayLog,
+    entry::{edit_day_log, get_log_file_path_for_date, list_log_dates},
+    git::{auto_sync_if_enabled, git_sync},
+    markdown::render_markdown,
+    theme::{Theme, layered},
+};
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::{
+        event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+        execute,
+        terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    },
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+};
+use regex::{Regex, RegexBuilder};
+use std::{
+    io::{self, IsTerminal},
+    mem,
+};
+use termcolor::{ColorSpec, WriteColor};
+
+/// Key help shown in the status line when there is no message.
+const HELP: &str = "←→ day  ↑↓ week  [] month  n/p next/prev log  t today  \
+                    / search  e edit  s sync  q quit";
+
+/// Width of the calendar and day list column, including borders.
+const SIDEBAR_WIDTH: u16 = 24;
+
+/// Opens the interactive journal browser.
+///
+/// Starts on today's log. The left column shows the selected month with
+/// logged days highlighted and a list of every logged day (or of the days
+/// matching the current search), newest first; the right column shows the
+/// selected day's log rendered as markdown.
+///
+/// Key bindings:
+/// - `←`/`→` or `h`/`l`: previous/next day
+/// - `↑`/`↓` or `k`/`j`: previous/next week
+/// - `[`/`]`: previous/next month
+/// - `p`/`n`: previous/next logged day, or search match
+/// - `t`: jump to today
+/// - `PageUp`/`PageDown` or `K`/`J`: scroll the log
+/// - `/`: search all logs (case-insensitive); `Esc` clears the search
+/// - `e`: edit the selected day in `$EDITOR`
+/// - `s`: sync with git
+/// - `q`: quit
+///
+/// # Arguments
+///
+/// * `config` - Application configuration (log directory and git settings)
+/// * `theme` - The styles to use
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - Stdout is not a terminal
+/// - Log files cannot be read or written
+/// - The terminal cannot be set up or drawn to
+///
+/// # Example
+///
+/// ```rust
+/// use dailylog::config::load_config;
+/// use dailylog::theme::Theme;
+/// use dailylog::tui::run_tui;
+///
+/// let config = load_config()?;
+/// run_tui(&config, &Theme::from_config(&config.theme)?)?;
+/// ```
+pub fn run_tui(config: &Config, theme: &Theme) -> anyhow::Result<()> {
+    if !io::stdout().is_terminal() {
+        return Err(anyhow::anyhow!("dailylog tui needs to run in a terminal"));
+    }
+
+    let mut app = App::new(config, theme)?;
+    let mut terminal = ratatui::try_init()?;
+    let result = app.run(&mut terminal);
+    ratatui::try_restore()?;
+    result
+}
+
+/// State of the journal browser.
+struct App<'a> {
+    /// Application configuration
+    config: &'a Config,
+    /// The styles to use
+    theme: &'a Theme,
+    /// The selected day
+    selected: NaiveDate,
+    /// The selected day's log
+    day: DayLog,
+    /// Days with at least one entry and their entry counts, newest first
+    logged: Vec<(NaiveDate, usize)>,
+    /// The last search and the days matching it, newest first
+    search: Option<(String, Vec<(NaiveDate, usize)>)>,
+    /// The search being typed, if the search prompt is open
+    input: Option<String>,
+    /// Lines scrolled past at the top of the log pane
+    scroll: u16,
+    /// Message shown in the status line until the next key press
+    status: Option<String>,
+    /// Set when the user quits
+    quit: bool,
+}
+
+impl<'a> App<'a> {
+    /// Loads the log directory and selects today.
+    fn new(config: &'a Config, theme: &'a Theme) -> anyhow::Result<Self> {
+        let selected = today();
+        let mut app = App {
+            config,
+            theme,
+            selected,
+            day: DayLog::load(&config.log_dir, selected)?,
+            logged: Vec::new(),
+            search: None,
+            input: None,
+            scroll: 0,
+            status: None,
+            quit: false,
+        };
+        app.reload()?;
+        Ok(app)
+    }
+
+    /// Re-reads the list of logged days and the selected day's log.
+    fn reload(&mut self) -> anyhow::Result<()> {
+        self.logged = self.find_days(None)?;
+        if let Some((query, _)) = self.search.take() {
+            let pattern = search_pattern(&query)?;
+            self.search = Some((query, self.find_days(Some(&pattern))?));
+        }
+        self.day = DayLog::load(&self.config.log_dir, self.selected)?;
+        Ok(())
+    }
+
+    /// Returns the days with entries matching `pattern` (or any entries), newest first.
+    fn find_days(&self, pattern: Option<&Regex>) -> anyhow::Result<Vec<(NaiveDate, usize)>> {
+        let mut days = Vec::new();
+        for date in list_log_dates(&self.config.log_dir)?.into_iter().rev() {
+            let day = DayLog::load(&self.config.log_dir, date)?;
+            let count = day
+                .entries
+                .iter()
+                .filter(|entry| {
+                    pattern.is_none_or(|pattern| {
+                        entry.header().is_some_and(|header| pattern.is_match(&header))
+                            || pattern.is_match(&entry.body)
+                    })
+                })
+                .count();
+            if count > 0 {
+                days.push((date, count));
+            }
+        }
+        Ok(days)
+    }
+
+    /// The days listed in the sidebar: search matches, or every logged day.
+    fn listed(&self) -> &[(NaiveDate, usize)] {
+        match &self.search {
+            Some((_, matches)) => matches,
+            None => &self.logged,
+        }
+    }
+
+    /// Selects a day and loads its log.
+    fn select(&mut self, date: NaiveDate) -> anyhow::Result<()> {
+        self.selected = date;
+        self.day = DayLog::load(&self.config.log_dir, date)?;
+        self.scroll = 0;
+        Ok(())
+    }
+
+    /// Draws frames and handles key presses until the user quits.
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> anyhow::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                self.status = None;
+                if self.input.is_some() {
+                    self.handle_search_key(key)?;
+                } else {
+                    self.handle_key(key, terminal)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles a key press while browsing.
+    fn handle_key(&mut self, key: KeyEvent, terminal: &mut DefaultTerminal) -> anyhow::Result<()> {
+        let date = self.selected;
+        match key.code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => self.quit = true,
+            KeyCode::Left | KeyCode::Char('h') => self.select(date - Duration::days(1))?,
+            KeyCode::Right | KeyCode::Char('l') => self.select(date + Duration::days(1))?,
+            KeyCode::Up | KeyCode::Char('k') => self.select(date - Duration::weeks(1))?,
+            KeyCode::Down | KeyCode::Char('j') => self.select(date + Duration::weeks(1))?,
+            KeyCode::Char('[') => self.select(date - Months::new(1))?,
+            KeyCode::Char(']') => self.select(date + Months::new(1))?,
+            KeyCode::Char('t') => self.select(today())?,
+            KeyCode::Char('n') => {
+                // The list is newest first, so the next day is the last later one
+                match self.listed().iter().rev().find(|(day, _)| *day > date) {
+                    Some(&(day, _)) => self.select(day)?,
+                    None => self.status = Some("No later logs".to_string()),
+                }
+            }
+            KeyCode::Char('p') => match self.listed().iter().find(|(day, _)| *day < date) {
+                Some(&(day, _)) => self.select(day)?,
+                None => self.status = Some("No earlier logs".to_string()),
+            },
+            KeyCode::PageDown | KeyCode::Char('J') => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::PageUp | KeyCode::Char('K') => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Char('/') => self.input = Some(String::new()),
+            KeyCode::Esc if self.search.is_some() => self.search = None,
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Char('e') => self.edit(terminal)?,
+            KeyCode::Char('s') => self.sync(terminal)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handles a key press while the search prompt is open.
+    fn handle_search_key(&mut self, key: KeyEvent) -> anyhow::Result<()> {
+        let Some(input) = &mut self.input else {
+            return Ok(());
+        };
+        match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.input = None,
+            KeyCode::Enter => {
+                let query = mem::take(input);
+                self.input = None;
+                if query.trim().is_empty() {
+                    self.search = None;
+                    return Ok(());
+                }
+                let matches = self.find_days(Some(&search_pattern(&query)?))?;
+                match matches.first() {
+                    Some(&(date, _)) => {
+                        self.select(date)?;
+                        self.search = Some((query, matches));
+                    }
+                    None => self.status = Some(format!("No entries match '{}'", query)),
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Opens the selected day's log in the editor, then reloads it.
+    fn edit(&mut self, terminal: &mut DefaultTerminal) -> anyhow::Result<()> {
+        let log_path = get_log_file_path_for_date(&self.config.log_dir, self.selected);
+        let result = suspended(terminal, || {
+            let outcome =
+                edit_day_log(&self.config.log_dir, self.selected, self.config.editor.as_deref(), false)?;
+            auto_sync_if_enabled(self.config)?;
+            anyhow::Ok(outcome)
+        })?;
+        self.status = Some(match result {
+            Ok(outcome) => outcome.message(self.selected, &log_path),
+            Err(e) => format!("Edit failed: {}", e),
+        });
+        self.reload()
+    }
+
+    /// Syncs the log directory with git, then reloads it.
+    fn sync(&mut self, terminal: &mut DefaultTerminal) -> anyhow::Result<()> {
+        // git prints progress and may ask for credentials, so give it the terminal
+        let result = suspended(terminal, || git_sync(self.config))?;
+        self.status = Some(match result {
+            Ok(()) => "Synced with git".to_string(),
+            Err(e) => format!("Sync failed: {}", e),
+        });
+        self.reload()
+    }
+
+    /// Draws the whole screen.
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [sidebar, log] =
+            Layout::horizontal([Constraint::Length(SIDEBAR_WIDTH), Constraint::Min(0)]).areas(main);
+        let [calendar, list] =
+            Layout::vertical([Constraint::Length(10), Constraint::Min(0)]).areas(sidebar);
+
+        self.draw_calendar(frame, calendar);
+        self.draw_list(frame, list);
+        self.draw_log(frame, log);
+        self.draw_status(frame, status);
+    }
+
+    /// Draws the selected month with logged days and search matches highlighted.
+    fn draw_calendar(&self, frame: &mut Frame, area: Rect) {
+        let today = today();
+        let first = self.selected.with_day(1).unwrap_or(self.selected);
+        let days_in_month = (first + Months::new(1) - first).num_days() as u32;
+        let mut lines = vec![Line::styled(
+            "Mo Tu We Th Fr Sa Su",
+            style_from_spec(&self.theme.muted),
+        )];
+
+        let mut cells = vec![Span::raw("   "); first.weekday().num_days_from_monday() as usize];
+        for day in 1..=days_in_month {
+            let date = first.with_day(day).unwrap_or(first);
+            let matched = self
+                .search
+                .as_ref()
+                .is_some_and(|(_, matches)| matches.iter().any(|(d, _)| *d == date));
+            let logged = self.logged.iter().any(|(d, _)| *d == date);
+
+            let mut spec = if matched {
+                self.theme.highlight.clone()
+            } else if logged {
+                layered(&self.theme.chart, ColorSpec::new().set_bold(true))
+            } else if date > today {
+                self.theme.muted.clone()
+            } else {
+                ColorSpec::new()
+            };
+            if date == today {
+                spec.set_underline(true);
+            }
+            let mut style = style_from_spec(&spec);
+            if date == self.selected {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+
+            cells.push(Span::styled(format!("{:>2}", day), style));
+            if date.weekday() == Weekday::Sun {
+                lines.push(Line::from(mem::take(&mut cells)));
+            } else {
+                cells.push(Span::raw(" "));
+            }
+        }
+        if !cells.is_empty() {
+            lines.push(Line::from(cells));
+        }
+
+        let title = Line::styled(
+            format!(" {} ", self.selected.format("%B %Y")),
+            style_from_spec(&self.theme.header),
+        );
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(title)), area);
+    }
+
+    /// Draws the list of logged days or search matches.
+    fn draw_list(&self, frame: &mut Frame, area: Rect) {
+        let listed = self.listed();
+        let items: Vec<ListItem> = listed
+            .iter()
+            .map(|(date, count)| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        date.format("%Y-%m-%d %a").to_string(),
+                        style_from_spec(&self.theme.date_header),
+                    ),
+                    Span::styled(format!(" {:>3}", count), style_from_spec(&self.theme.muted)),
+                ]))
+            })
+            .collect();
+
+        let title = match &self.search {
+            Some((query, matches)) => format!(" /{} ({}) ", query, matches.len()),
+            None => format!(" Logs ({}) ", listed.len()),
+        };
+        let list = List::new(items)
+            .block(Block::bordered().title(Line::styled(title, style_from_spec(&self.theme.header))))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+
+        let mut state = ListState::default()
+            .with_selected(listed.iter().position(|(date, _)| *date == self.selected));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    /// Draws the selected day's log rendered as markdown.
+    fn draw_log(&mut self, frame: &mut Frame, area: Rect) {
+        let title = Line::styled(
+            format!(" {} ", self.selected.format("%A %Y-%m-%d")),
+            style_from_spec(&self.theme.date_header),
+        );
+        let block = Block::bordered().title(title);
+        let width = block.inner(area).width as usize;
+
+        let text = if self.day.is_empty() {
+            Text::styled(
+                "No log for this day. Press e to write one.",
+                style_from_spec(&self.theme.muted),
+            )
+        } else {
+            let mut writer = SpanWriter::default();
+            // Rendering into memory cannot fail
+            let _ = render_markdown(&mut writer, &self.day.to_markdown(), Some(width), self.theme);
+            writer.into_text()
+        };
+
+        let max_scroll = text.height().saturating_sub(block.inner(area).height as usize);
+        self.scroll = self.scroll.min(max_scroll.try_into().unwrap_or(u16::MAX));
+        frame.render_widget(Paragraph::new(text).block(block).scroll((self.scroll, 0)), area);
+    }
+
+    /// Draws the search prompt, the last message or the key help.
+    fn draw_status(&self, frame: &mut Frame, area: Rect) {
+        let line = if let Some(input) = &self.input {
+            Line::from(format!("/{}█", input))
+        } else if let Some(status) = &self.status {
+            Line::from(status.as_str())
+        } else {
+            Line::styled(HELP, style_from_spec(&self.theme.muted))
+        };
+        frame.render_widget(Paragraph::new(line), area);
+    }
+}
+
+/// Builds a case-insensitive pattern matching `query` literally.
+fn search_pattern(query: &str) -> anyhow::Result<Regex> {
+    RegexBuilder::new(&regex::escape(query.trim()))
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Invalid search: {}", e))
+}
+
+/// Leaves the full-screen view while `action` runs, then restores it.
+fn suspended<T>(terminal: &mut DefaultTerminal, action: impl FnOnce() -> T) -> anyhow::Result<T> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen)?;
+    let result = action();
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    enable_raw_mode()?;
+    terminal.clear()?;
+    Ok(result)
+}
+
+/// Converts a termcolor style into a ratatui style.
+fn style_from_spec(spec: &ColorSpec) -> Style {
+    let mut style = Style::new();
+    if let Some(fg) = spec.fg() {
+        style = style.fg(color(*fg, spec.intense()));
+    }
+    if let Some(bg) = spec.bg() {
+        style = style.bg(color(*bg, false));
+    }
+    let modifiers = [
+        (spec.bold(), Modifier::BOLD),
+        (spec.italic(), Modifier::ITALIC),
+        (spec.underline(), Modifier::UNDERLINED),
+        (spec.dimmed(), Modifier::DIM),
+        (spec.strikethrough(), Modifier::CROSSED_OUT),
+    ];
+    for (enabled, modifier) in modifiers {
+        if enabled {
+            style = style.add_modifier(modifier);
+        }
+    }
+    style
+}
+
+/// Converts a termcolor color into a ratatui color.
+fn color(color: termcolor::Color, intense: bool) -> Color {
+    use termcolor::Color as Term;
+    match (color, intense) {
+        (Term::Black, false) => Color::Black,
+        (Term::Black, true) => Color::DarkGray,
+        (Term::Red, false) => Color::Red,
+        (Term::Red, true) => Color::LightRed,
+        (Term::Green, false) => Color::Green,
+        (Term::Green, true) => Color::LightGreen,
+        (Term::Yellow, false) => Color::Yellow,
+        (Term::Yellow, true) => Color::LightYellow,
+        (Term::Blue, false) => Color::Blue,
+        (Term::Blue, true) => Color::LightBlue,
+        (Term::Magenta, false) => Color::Magenta,
+        (Term::Magenta, true) => Color::LightMagenta,
+        (Term::Cyan, false) => Color::Cyan,
+        (Term::Cyan, true) => Color::LightCyan,
+        (Term::White, false) => Color::Gray,
+        (Term::White, true) => Color::White,
+        (Term::Ansi256(index), _) => Color::Indexed(index),
+        (Term::Rgb(r, g, b), _) => Color::Rgb(r, g, b),
+        _ => Color::Reset,
+    }
+}
+
+/// Collects colored output as ratatui text, so the terminal markdown
+/// renderer can draw into the log pane.
+#[derive(Default)]
+struct SpanWriter {
+    /// Completed lines
+    lines: Vec<Line<'static>>,
+    /// Spans of the line being written
+    current: Vec<Span<'static>>,
+    /// Style for text written next
+    style: Style,
+}
+
+impl SpanWriter {
+    /// Returns everything written so far.
+    fn into_text(mut self) -> Text<'static> {
+        if !self.current.is_empty() {
+            self.lines.push(Line::from(self.current));
+        }
+        Text::from(self.lines)
+    }
+}
+
+impl io::Write for SpanWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        for (i, part) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.lines.push(Line::from(mem::take(&mut self.current)));
+            }
+            if !part.is_empty() {
+                self.current.push(Span::styled(part.to_string(), self.style));
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl WriteColor for SpanWriter {
+    fn supports_color(&self) -> bool {
+        true
+    }
+
+    fn set_color(&mut self, spec: &ColorSpec) -> io::Result<()> {
+        self.style = style_from_spec(spec);
+        Ok(())
+    }
+
+    fn reset(&mut self) -> io::Result<()> {
+        self.style = Style::new();
+        Ok(())
+    }
+}